@@ -0,0 +1,96 @@
+use iced::canvas::{Cursor, Event, Program};
+use iced::{mouse, Point, Rectangle, Size};
+use iced_charts_lib::charts::axis_enums::{BarChartDataAxis, XAxisOrientation, YAxisOrientation};
+use iced_charts_lib::charts::bar_chart_data::{BarChartData, Dataset};
+use iced_charts_lib::charts::bar_chart_iced_struct::BarChartIcedStruct;
+use iced_charts_lib::charts::bar_chart_style_info_iced::BarChartStyleInfoIced;
+use std::time::{Duration, Instant};
+
+const DATASET_AMOUNT: u32 = 10;
+const CLUSTER_AMOUNT: usize = 500;
+const FRAME_AMOUNT: u32 = 200;
+const HEADLINE: &str = "Hover frame time";
+
+/// Times `draw` after hover events for a chart with 5000 bars, once with all caches redrawn
+/// every frame like the former single chart cache, once with only the invalidated caches.
+/// Run with `cargo run --release --example hover_frame_time`.
+fn main() {
+    let bounds = Rectangle::new(Point::ORIGIN, Size::new(1600.0, 900.0));
+
+    let mut chart = create_chart();
+    let all_caches = time_hover_frames(&mut chart, bounds, true);
+    let mut chart = create_chart();
+    let split_caches = time_hover_frames(&mut chart, bounds, false);
+
+    println!(
+        "{} bars, {} hover frames",
+        DATASET_AMOUNT as usize * CLUSTER_AMOUNT,
+        FRAME_AMOUNT
+    );
+    println!(
+        "all caches redrawn:  {:?} per frame",
+        all_caches / FRAME_AMOUNT
+    );
+    println!(
+        "split caches:        {:?} per frame",
+        split_caches / FRAME_AMOUNT
+    );
+}
+
+fn create_chart() -> BarChartIcedStruct {
+    let datasets = (0..DATASET_AMOUNT)
+        .map(|id| {
+            let values = (0..CLUSTER_AMOUNT)
+                .map(|cluster_index| ((cluster_index as u32 * 7 + id * 13) % 100) as f32 + 1.0)
+                .collect();
+            Dataset::new(id, format!("dataset {}", id), values)
+        })
+        .collect();
+    let cluster_names = (0..CLUSTER_AMOUNT)
+        .map(|cluster_index| format!("cluster {}", cluster_index))
+        .collect();
+    let mut chart = BarChartIcedStruct::new(
+        BarChartDataAxis::XPositive,
+        XAxisOrientation::Positive,
+        YAxisOrientation::Positive,
+        BarChartData::new(datasets),
+        cluster_names,
+        BarChartStyleInfoIced::new(),
+    )
+    .expect("the generated chart is valid");
+    chart.set_headline(String::from(HEADLINE));
+    chart
+}
+
+/// Moves the cursor diagonally over the chart and returns the total time spent in `draw`.
+/// `redraw_all` clears every cache and the layout before each frame.
+fn time_hover_frames(
+    chart: &mut BarChartIcedStruct,
+    bounds: Rectangle,
+    redraw_all: bool,
+) -> Duration {
+    // the first frame calculates the layout and fills the caches in both cases
+    Program::draw(chart, bounds, Cursor::Unavailable);
+
+    let mut total = Duration::default();
+    for frame in 0..FRAME_AMOUNT {
+        let progress = frame as f32 / FRAME_AMOUNT as f32;
+        let position = Point::new(bounds.width * progress, bounds.height * progress);
+        Program::update(
+            chart,
+            Event::Mouse(mouse::Event::CursorMoved {
+                x: position.x,
+                y: position.y,
+            }),
+            bounds,
+            Cursor::Available(position),
+        );
+        if redraw_all {
+            chart.set_headline(String::from(HEADLINE));
+        }
+        let start = Instant::now();
+        Program::draw(chart, bounds, Cursor::Available(position));
+        total += start.elapsed();
+    }
+    total
+}
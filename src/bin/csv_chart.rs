@@ -0,0 +1,208 @@
+use iced::Size;
+use iced_charts_lib::charts::axis_enums::{BarChartDataAxis, XAxisOrientation, YAxisOrientation};
+use iced_charts_lib::charts::bar_chart_data::{BarChartData, CsvDataOrientation, CsvImportOptions};
+use iced_charts_lib::charts::bar_chart_iced_struct::BarChartIcedStruct;
+use iced_charts_lib::charts::bar_chart_style_info_iced::BarChartStyleInfoIced;
+use iced_charts_lib::charts::pdf_renderer::PdfRenderer;
+use iced_charts_lib::charts::png_renderer::PngRenderer;
+use iced_charts_lib::charts::scene::SceneChart;
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::process;
+
+const USAGE: &str =
+    "Usage: csv_chart [OPTIONS] <INPUT.csv | -> <OUTPUT.svg | OUTPUT.png | OUTPUT.pdf>
+
+Reads a CSV file with a header row. By default the first column holds the cluster names
+and every further column is one dataset, named after its header.
+
+Options:
+    --datasets <columns|rows>    whether datasets are columns or rows   (default: columns)
+    --delimiter <CHARACTER>                                             (default: ,)
+    --data-axis <x-positive|x-negative|y-positive|y-negative>   (default: x-positive)
+    --x-axis <positive|negative|complete>                       (default: positive)
+    --y-axis <positive|negative|complete>                       (default: positive)
+    --headline <TEXT>
+    --width <PIXELS>                                            (default: 800)
+    --height <PIXELS>                                           (default: 600)
+    --scale <FACTOR>     pixels per unit for PNG output         (default: 1)
+    --font <FILE>        TrueType/OpenType font for the text, required for PNG and PDF output
+    --help";
+
+#[derive(Debug)]
+struct Options {
+    input: String,
+    output: String,
+    csv_import_options: CsvImportOptions,
+    data_axis: BarChartDataAxis,
+    x_axis_orientation: XAxisOrientation,
+    y_axis_orientation: YAxisOrientation,
+    headline: Option<String>,
+    width: f32,
+    height: f32,
+    scale_factor: f32,
+    font: Option<String>,
+}
+
+fn main() {
+    let arguments: Vec<String> = env::args().skip(1).collect();
+    if arguments.iter().any(|argument| argument == "--help") {
+        println!("{}", USAGE);
+        return;
+    }
+    let result = parse_arguments(&arguments).and_then(|options| run(&options));
+    if let Err(error) = result {
+        eprintln!("error: {}\n\n{}", error, USAGE);
+        process::exit(1);
+    }
+}
+
+fn run(options: &Options) -> Result<(), String> {
+    let input: Box<dyn Read> = if options.input == "-" {
+        Box::new(io::stdin())
+    } else {
+        Box::new(
+            File::open(&options.input)
+                .map_err(|error| format!("Could not read {}: {}", options.input, error))?,
+        )
+    };
+    let (data, cluster_names) = BarChartData::<f64>::from_csv(input, &options.csv_import_options)
+        .map_err(|error| error.to_string())?;
+
+    let mut chart = BarChartIcedStruct::new(
+        options.data_axis,
+        options.x_axis_orientation,
+        options.y_axis_orientation,
+        data,
+        cluster_names,
+        BarChartStyleInfoIced::new(),
+    )
+    .map_err(|error| error.to_string())?;
+    if let Some(headline) = &options.headline {
+        chart.set_headline(headline.clone());
+    }
+
+    let size = Size::new(options.width, options.height);
+    let output = options.output.to_lowercase();
+    if output.ends_with(".svg") {
+        chart.save_svg(size, &options.output)
+    } else if output.ends_with(".pdf") {
+        let font_data = read_font(options, "PDF")?;
+        let renderer = PdfRenderer::new(&font_data).map_err(|error| error.to_string())?;
+        chart.save_pdf(size, &renderer, &options.output)
+    } else if output.ends_with(".png") {
+        let font_data = read_font(options, "PNG")?;
+        let mut renderer = PngRenderer::new(&font_data).map_err(|error| error.to_string())?;
+        renderer.scale_factor = options.scale_factor;
+        chart.save_png(size, &renderer, &options.output)
+    } else {
+        return Err(format!(
+            "Unknown output format of {}, expected .svg, .png or .pdf",
+            options.output
+        ));
+    }
+    .map_err(|error| error.to_string())
+}
+
+/// returns the content of the font file, which the `format` output needs for its text
+fn read_font(options: &Options, format: &str) -> Result<Vec<u8>, String> {
+    let font_path = options
+        .font
+        .as_ref()
+        .ok_or_else(|| format!("{} output needs a font, see --font", format))?;
+    fs::read(font_path).map_err(|error| format!("Could not read {}: {}", font_path, error))
+}
+
+fn parse_arguments(arguments: &[String]) -> Result<Options, String> {
+    let mut positional = vec![];
+    let mut options = Options {
+        input: String::new(),
+        output: String::new(),
+        csv_import_options: CsvImportOptions::new(),
+        data_axis: BarChartDataAxis::XPositive,
+        x_axis_orientation: XAxisOrientation::Positive,
+        y_axis_orientation: YAxisOrientation::Positive,
+        headline: None,
+        width: 800.0,
+        height: 600.0,
+        scale_factor: 1.0,
+        font: None,
+    };
+
+    let mut arguments = arguments.iter();
+    while let Some(argument) = arguments.next() {
+        if !argument.starts_with("--") {
+            positional.push(argument.clone());
+            continue;
+        }
+        let value = arguments
+            .next()
+            .ok_or_else(|| format!("Missing value for {}", argument))?;
+        match argument.as_str() {
+            "--datasets" => {
+                options.csv_import_options.orientation = match value.as_str() {
+                    "columns" => CsvDataOrientation::DatasetsInColumns,
+                    "rows" => CsvDataOrientation::DatasetsInRows,
+                    _ => return Err(format!("Unknown dataset orientation {}", value)),
+                }
+            }
+            "--delimiter" => {
+                options.csv_import_options.delimiter = match value.as_bytes() {
+                    [delimiter] => *delimiter,
+                    _ => return Err(format!("The delimiter must be one byte, got {}", value)),
+                }
+            }
+            "--data-axis" => {
+                options.data_axis = match value.as_str() {
+                    "x-positive" => BarChartDataAxis::XPositive,
+                    "x-negative" => BarChartDataAxis::XNegative,
+                    "y-positive" => BarChartDataAxis::YPositive,
+                    "y-negative" => BarChartDataAxis::YNegative,
+                    _ => return Err(format!("Unknown data axis {}", value)),
+                }
+            }
+            "--x-axis" => {
+                options.x_axis_orientation = match value.as_str() {
+                    "positive" => XAxisOrientation::Positive,
+                    "negative" => XAxisOrientation::Negative,
+                    "complete" => XAxisOrientation::Complete,
+                    _ => return Err(format!("Unknown x axis orientation {}", value)),
+                }
+            }
+            "--y-axis" => {
+                options.y_axis_orientation = match value.as_str() {
+                    "positive" => YAxisOrientation::Positive,
+                    "negative" => YAxisOrientation::Negative,
+                    "complete" => YAxisOrientation::Complete,
+                    _ => return Err(format!("Unknown y axis orientation {}", value)),
+                }
+            }
+            "--headline" => options.headline = Some(value.clone()),
+            "--width" => options.width = parse_positive_number(argument, value)?,
+            "--height" => options.height = parse_positive_number(argument, value)?,
+            "--scale" => options.scale_factor = parse_positive_number(argument, value)?,
+            "--font" => options.font = Some(value.clone()),
+            _ => return Err(format!("Unknown option {}", argument)),
+        }
+    }
+
+    match positional.as_slice() {
+        [input, output] => {
+            options.input = input.clone();
+            options.output = output.clone();
+            Ok(options)
+        }
+        _ => Err(String::from("Expected an input and an output file")),
+    }
+}
+
+fn parse_positive_number(argument: &str, value: &str) -> Result<f32, String> {
+    match value.parse::<f32>() {
+        Ok(number) if number > 0.0 && number.is_finite() => Ok(number),
+        _ => Err(format!(
+            "{} expects a positive number, got {}",
+            argument, value
+        )),
+    }
+}
@@ -0,0 +1,58 @@
+use crate::charts::chart_value::ChartValue;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// How the values of all records falling into one bar are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Aggregation {
+    Sum,
+    Mean,
+    /// the amount of records, the values themselves are ignored
+    Count,
+    Min,
+    Max,
+    Median,
+}
+
+impl Aggregation {
+    /// Returns `None` for no values, except for `Count`, which is 0 then. Sum, min, max and
+    /// median keep the type of the values, sums of integers stop at their bounds. The mean and
+    /// the median of an even amount of values are computed in f64, integer types get them rounded.
+    pub fn apply<T: ChartValue>(&self, values: &[T]) -> Option<T> {
+        let (first, rest) = match values.split_first() {
+            Some(split) => split,
+            None => {
+                return match self {
+                    Aggregation::Count => Some(T::from_f64(0.0)),
+                    _ => None,
+                }
+            }
+        };
+        let result = match self {
+            Aggregation::Sum => rest
+                .iter()
+                .fold(*first, |sum, value| sum.saturating_add(*value)),
+            Aggregation::Mean => T::from_f64(
+                values.iter().map(|value| value.to_f64()).sum::<f64>() / values.len() as f64,
+            ),
+            Aggregation::Count => T::from_f64(values.len() as f64),
+            Aggregation::Min => rest
+                .iter()
+                .fold(*first, |min, value| if *value < min { *value } else { min }),
+            Aggregation::Max => rest
+                .iter()
+                .fold(*first, |max, value| if *value > max { *value } else { max }),
+            Aggregation::Median => {
+                let mut sorted = values.to_vec();
+                sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+                let middle = sorted.len() / 2;
+                if sorted.len() % 2 == 1 || sorted[middle - 1] == sorted[middle] {
+                    sorted[middle]
+                } else {
+                    T::from_f64((sorted[middle - 1].to_f64() + sorted[middle].to_f64()) / 2.0)
+                }
+            }
+        };
+        Some(result)
+    }
+}
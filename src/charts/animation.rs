@@ -0,0 +1,102 @@
+use iced::Rectangle;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Maps the elapsed part of an animation to the progress of the animated values, both from 0.0 to 1.0.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    pub fn apply(&self, time: f32) -> f32 {
+        let time = time.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => time,
+            Easing::EaseIn => time * time,
+            Easing::EaseOut => 1.0 - (1.0 - time) * (1.0 - time),
+            Easing::EaseInOut => time * time * (3.0 - 2.0 * time),
+        }
+    }
+}
+
+/// Transition from the bars shown before a data change to the new ones.
+/// The clock starts with the first tick, so the first frame shows the old state.
+#[derive(Debug, Clone)]
+pub(crate) struct BarAnimation {
+    duration: Duration,
+    easing: Easing,
+    started: Option<Instant>,
+    /// eased progress from 0.0 to 1.0
+    progress: f32,
+    /// bar rectangles before the change, by (dataset_id, cluster_index), see `BarHitInfo`
+    from_bars: HashMap<(u32, Option<usize>), Rectangle>,
+    /// biggest value of the value axis before the change
+    from_biggest_value: f64,
+}
+
+impl BarAnimation {
+    pub(crate) fn new(
+        duration: Duration,
+        easing: Easing,
+        from_bars: HashMap<(u32, Option<usize>), Rectangle>,
+        from_biggest_value: f64,
+    ) -> Self {
+        BarAnimation {
+            duration,
+            easing,
+            started: None,
+            progress: 0.0,
+            from_bars,
+            from_biggest_value,
+        }
+    }
+
+    /// returns true once the animation is finished
+    pub(crate) fn advance(&mut self, now: Instant) -> bool {
+        let started = *self.started.get_or_insert(now);
+        let elapsed = now.saturating_duration_since(started);
+        if self.duration.as_secs_f32() <= 0.0 || elapsed >= self.duration {
+            self.progress = 1.0;
+            return true;
+        }
+        self.progress = self
+            .easing
+            .apply(elapsed.as_secs_f32() / self.duration.as_secs_f32());
+        false
+    }
+
+    pub(crate) fn interpolate_biggest_value(&self, to_biggest_value: f64) -> f64 {
+        self.from_biggest_value
+            + (to_biggest_value - self.from_biggest_value) * self.progress as f64
+    }
+
+    /// `collapsed` is where the bar starts if it didn't exist before the change
+    pub(crate) fn interpolate_bar(
+        &self,
+        dataset_id: u32,
+        cluster_index: Option<usize>,
+        to: Rectangle,
+        collapsed: Rectangle,
+    ) -> Rectangle {
+        let from = self
+            .from_bars
+            .get(&(dataset_id, cluster_index))
+            .copied()
+            .unwrap_or(collapsed);
+        Rectangle {
+            x: lerp(from.x, to.x, self.progress),
+            y: lerp(from.y, to.y, self.progress),
+            width: lerp(from.width, to.width, self.progress),
+            height: lerp(from.height, to.height, self.progress),
+        }
+    }
+}
+
+fn lerp(from: f32, to: f32, progress: f32) -> f32 {
+    from + (to - from) * progress
+}
@@ -1,4 +1,6 @@
-#[derive(Debug, Clone, Copy, PartialOrd, PartialEq)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialOrd, PartialEq, Serialize, Deserialize)]
 pub enum AxisOrientation {
     XPositiveYPositive,
     XPositiveYNegative,
@@ -11,20 +13,20 @@ pub enum AxisOrientation {
     XCompleteYComplete,
 }
 
-#[derive(Debug, Clone, Copy, PartialOrd, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialOrd, PartialEq, Serialize, Deserialize)]
 pub enum XAxisOrientation {
     Positive,
     Negative,
     Complete,
 }
 
-#[derive(Debug, Clone, Copy, PartialOrd, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialOrd, PartialEq, Serialize, Deserialize)]
 pub enum YAxisOrientation {
     Positive,
     Negative,
     Complete,
 }
-#[derive(Debug, Clone, Copy, PartialOrd, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialOrd, PartialEq, Serialize, Deserialize)]
 pub enum BarChartDataAxis {
     XPositive,
     XNegative,
@@ -32,7 +34,7 @@ pub enum BarChartDataAxis {
     YNegative,
 }
 
-#[derive(Debug, Clone, Copy, PartialOrd, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialOrd, PartialEq, Serialize, Deserialize)]
 pub enum BarChartDataPositivity {
     Positive,
     Negative,
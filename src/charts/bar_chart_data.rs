@@ -1,28 +1,185 @@
-use crate::charts::error::ChartsLibError;
+use crate::charts::aggregation::Aggregation;
+use crate::charts::chart_value::ChartValue;
+use crate::charts::error::{ChartsLibError, ErrorKind};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Read;
 
-#[derive(Debug, Clone)]
-pub struct Dataset {
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Dataset<T = f32> {
     pub id: u32,
     pub name: String,
-    pub data: Vec<f32>,
+    /// `None` marks a missing value, which is drawn as a gap instead of a bar
+    pub data: Vec<Option<T>>,
 }
 
+/// Datasets of any `ChartValue` type, e.g. `BarChartData<u64>` for big counters.
 #[derive(Debug)]
-pub struct BarChartData {
-    pub datasets: Vec<Dataset>,
+pub struct BarChartData<T = f32> {
+    pub datasets: Vec<Dataset<T>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CsvDataOrientation {
+    /// The header row holds the dataset names, the first column the cluster names.
+    DatasetsInColumns,
+    /// The header row holds the cluster names, the first column the dataset names.
+    DatasetsInRows,
+}
+
+#[derive(Debug, Clone)]
+pub struct CsvImportOptions {
+    pub orientation: CsvDataOrientation,
+    pub delimiter: u8,
+}
+
+impl CsvImportOptions {
+    pub fn new() -> Self {
+        CsvImportOptions {
+            orientation: CsvDataOrientation::DatasetsInColumns,
+            delimiter: b',',
+        }
+    }
 }
 
-impl Dataset {
-    pub fn new(id: u32, name: String, data: Vec<f32>) -> Self {
+impl<T: ChartValue> Dataset<T> {
+    pub fn new(id: u32, name: String, data: Vec<T>) -> Self {
+        Dataset {
+            id,
+            name,
+            data: data.into_iter().map(Some).collect(),
+        }
+    }
+
+    pub fn with_missing_values(id: u32, name: String, data: Vec<Option<T>>) -> Self {
         Dataset { id, name, data }
     }
+
+    /// returns the value of the cluster, `None` if it is missing
+    pub fn get_value(&self, cluster_index: usize) -> Option<T> {
+        self.data.get(cluster_index).copied().flatten()
+    }
+
+    /// Checks that the dataset has an entry for each of the `cluster_amount` clusters and no
+    /// value is non-finite. Entries may be missing values. Always passes if `lenient`.
+    pub fn validate(&self, cluster_amount: usize, lenient: bool) -> Result<(), ChartsLibError> {
+        if lenient {
+            return Ok(());
+        }
+        if self.data.len() != cluster_amount {
+            return Err(ChartsLibError::new(
+                ErrorKind::DatasetToAddIsNotTheSameLengthAsExistingDatasets,
+                format!(
+                    "The dataset {} has {} values for {} clusters.",
+                    self.id,
+                    self.data.len(),
+                    cluster_amount
+                ),
+            ));
+        }
+        for (cluster_index, value) in self.data.iter().enumerate() {
+            validate_value(*value, self.id, cluster_index, lenient)?;
+        }
+        Ok(())
+    }
+
+    /// turns NaN and infinite values into missing values
+    pub fn replace_non_finite_values(&mut self) {
+        for value in &mut self.data {
+            if matches!(value, Some(number) if !number.is_finite()) {
+                *value = None;
+            }
+        }
+    }
 }
 
-impl BarChartData {
-    pub fn new(datasets: Vec<Dataset>) -> Self {
+impl<T: ChartValue> BarChartData<T> {
+    pub fn new(datasets: Vec<Dataset<T>>) -> Self {
         BarChartData { datasets }
     }
 
+    /// Reads datasets from a CSV with a header row, ids are assigned in order starting at 0.
+    /// returns the data and the cluster names
+    pub fn from_csv<R: Read>(
+        reader: R,
+        options: &CsvImportOptions,
+    ) -> Result<(BarChartData<T>, Vec<String>), ChartsLibError> {
+        let mut csv_reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .delimiter(options.delimiter)
+            .from_reader(reader);
+        let mut records = csv_reader.records();
+
+        let header = match records.next() {
+            Some(header) => header.map_err(csv_read_error)?,
+            None => {
+                return Err(ChartsLibError::new(
+                    ErrorKind::InvalidCsvStructure,
+                    String::from("The CSV is empty."),
+                ))
+            }
+        };
+        if header.len() < 2 {
+            return Err(ChartsLibError::new(
+                ErrorKind::InvalidCsvStructure,
+                String::from("The CSV needs a name column and at least one value column."),
+            ));
+        }
+        let header_names: Vec<String> = header.iter().skip(1).map(String::from).collect();
+
+        let mut row_names = vec![];
+        let mut rows: Vec<Vec<Option<T>>> = vec![];
+        for record in records {
+            let record = record.map_err(csv_read_error)?;
+            let line = record.position().map_or(0, |position| position.line());
+            row_names.push(String::from(record.get(0).unwrap_or("")));
+            let mut row = Vec::with_capacity(header_names.len());
+            for (column_index, field) in record.iter().enumerate().skip(1) {
+                let field = field.trim();
+                if field.is_empty() {
+                    row.push(None);
+                    continue;
+                }
+                let value = field.parse::<T>().map_err(|_| {
+                    ChartsLibError::new(
+                        ErrorKind::InvalidCsvValue {
+                            line,
+                            column: column_index + 1,
+                        },
+                        format!("\"{}\" is not a number.", field),
+                    )
+                })?;
+                row.push(Some(value));
+            }
+            rows.push(row);
+        }
+
+        match options.orientation {
+            CsvDataOrientation::DatasetsInColumns => {
+                let datasets = header_names
+                    .into_iter()
+                    .enumerate()
+                    .map(|(column_index, name)| {
+                        let data = rows.iter().map(|row| row[column_index]).collect();
+                        Dataset::with_missing_values(column_index as u32, name, data)
+                    })
+                    .collect();
+                Ok((BarChartData::new(datasets), row_names))
+            }
+            CsvDataOrientation::DatasetsInRows => {
+                let datasets = row_names
+                    .into_iter()
+                    .zip(rows)
+                    .enumerate()
+                    .map(|(row_index, (name, data))| {
+                        Dataset::with_missing_values(row_index as u32, name, data)
+                    })
+                    .collect();
+                Ok((BarChartData::new(datasets), header_names))
+            }
+        }
+    }
+
     pub fn get_biggest_amount_of_data_entries_in_one_dataset(&self) -> usize {
         let mut result = 0;
         for dataset in &self.datasets {
@@ -33,10 +190,12 @@ impl BarChartData {
         result
     }
 
-    pub fn get_biggest_data_entry_abs(&self) -> f32 {
+    /// computed in f64, so big integers keep their precision for the axis labels
+    pub fn get_biggest_data_entry_abs(&self) -> f64 {
         let mut result = 0.0;
         for dataset in &self.datasets {
-            for entry in &dataset.data {
+            for entry in dataset.data.iter().flatten() {
+                let entry = entry.to_f64();
                 if result < entry.abs() {
                     result = entry.abs();
                 }
@@ -44,4 +203,350 @@ impl BarChartData {
         }
         result
     }
+
+    /// Appends one value to every dataset, `values` in dataset order. Shorter datasets are padded
+    /// with missing values first, so the values form a new cluster behind all existing ones.
+    /// Drops the oldest clusters while there are more than `max_clusters`.
+    /// returns the amount of dropped clusters
+    pub fn push_cluster(
+        &mut self,
+        values: &[Option<T>],
+        max_clusters: Option<usize>,
+    ) -> Result<usize, ChartsLibError> {
+        self.check_cluster_value_count(values)?;
+        let cluster_amount = self.get_biggest_amount_of_data_entries_in_one_dataset();
+        let dropped = match max_clusters {
+            Some(max_clusters) => (cluster_amount + 1).saturating_sub(max_clusters),
+            None => 0,
+        };
+        for (dataset, value) in self.datasets.iter_mut().zip(values) {
+            dataset.data.resize(cluster_amount, None);
+            dataset.data.push(*value);
+            dataset.data.drain(..dropped.min(dataset.data.len()));
+        }
+        Ok(dropped)
+    }
+
+    /// Pads every dataset shorter than `cluster_amount` with missing values.
+    pub fn pad_clusters(&mut self, cluster_amount: usize) {
+        for dataset in &mut self.datasets {
+            if dataset.data.len() < cluster_amount {
+                dataset.data.resize(cluster_amount, None);
+            }
+        }
+    }
+
+    /// Checks for duplicate dataset ids and, unless `lenient`, for empty data and every
+    /// dataset having one finite value per cluster.
+    pub fn validate(&self, cluster_amount: usize, lenient: bool) -> Result<(), ChartsLibError> {
+        if !lenient && (self.datasets.is_empty() || cluster_amount == 0) {
+            return Err(empty_data_error());
+        }
+        for (index, dataset) in self.datasets.iter().enumerate() {
+            if self.datasets[..index]
+                .iter()
+                .any(|previous| previous.id == dataset.id)
+            {
+                return Err(duplicate_id_error(dataset.id));
+            }
+            dataset.validate(cluster_amount, lenient)?;
+        }
+        Ok(())
+    }
+
+    /// Checks that the dataset could be added, see `validate`.
+    pub fn validate_new_dataset(
+        &self,
+        dataset: &Dataset<T>,
+        cluster_amount: usize,
+        lenient: bool,
+    ) -> Result<(), ChartsLibError> {
+        if self
+            .datasets
+            .iter()
+            .any(|existing| existing.id == dataset.id)
+        {
+            return Err(duplicate_id_error(dataset.id));
+        }
+        dataset.validate(cluster_amount, lenient)
+    }
+
+    /// turns NaN and infinite values into missing values
+    pub fn replace_non_finite_values(&mut self) {
+        for dataset in &mut self.datasets {
+            dataset.replace_non_finite_values();
+        }
+    }
+
+    /// returns the position of the dataset with the id
+    pub fn get_dataset_index(&self, id: u32) -> Result<usize, ChartsLibError> {
+        self.datasets
+            .iter()
+            .position(|dataset| dataset.id == id)
+            .ok_or_else(|| {
+                ChartsLibError::new(
+                    ErrorKind::UnknownDatasetId { id },
+                    format!("There is no dataset with the id {}.", id),
+                )
+            })
+    }
+
+    pub fn remove_dataset(&mut self, id: u32) -> Result<Dataset<T>, ChartsLibError> {
+        let index = self.get_dataset_index(id)?;
+        Ok(self.datasets.remove(index))
+    }
+
+    pub fn set_dataset_name(&mut self, id: u32, name: String) -> Result<(), ChartsLibError> {
+        let index = self.get_dataset_index(id)?;
+        self.datasets[index].name = name;
+        Ok(())
+    }
+
+    /// returns the replaced value
+    pub fn update_value(
+        &mut self,
+        dataset_id: u32,
+        cluster_index: usize,
+        value: Option<T>,
+    ) -> Result<Option<T>, ChartsLibError> {
+        let index = self.get_dataset_index(dataset_id)?;
+        match self.datasets[index].data.get_mut(cluster_index) {
+            Some(entry) => Ok(std::mem::replace(entry, value)),
+            None => Err(cluster_index_error(cluster_index)),
+        }
+    }
+
+    /// Inserts one value into every dataset at `index`, `values` in dataset order.
+    /// Datasets shorter than `index` are padded with missing values first.
+    pub fn insert_cluster(
+        &mut self,
+        index: usize,
+        values: &[Option<T>],
+    ) -> Result<(), ChartsLibError> {
+        if index > self.get_biggest_amount_of_data_entries_in_one_dataset() {
+            return Err(cluster_index_error(index));
+        }
+        self.check_cluster_value_count(values)?;
+        for (dataset, value) in self.datasets.iter_mut().zip(values) {
+            if dataset.data.len() < index {
+                dataset.data.resize(index, None);
+            }
+            dataset.data.insert(index, *value);
+        }
+        Ok(())
+    }
+
+    /// Removes the value at `index` from every dataset that has one.
+    pub fn remove_cluster(&mut self, index: usize) -> Result<(), ChartsLibError> {
+        if index >= self.get_biggest_amount_of_data_entries_in_one_dataset() {
+            return Err(cluster_index_error(index));
+        }
+        for dataset in &mut self.datasets {
+            if index < dataset.data.len() {
+                dataset.data.remove(index);
+            }
+        }
+        Ok(())
+    }
+
+    /// Sorts the datasets into the order of `ids`, which has to contain every dataset id once.
+    pub fn reorder_datasets(&mut self, ids: &[u32]) -> Result<(), ChartsLibError> {
+        let mut indices = Vec::with_capacity(ids.len());
+        for id in ids {
+            let index = self.get_dataset_index(*id)?;
+            if indices.contains(&index) {
+                return Err(ChartsLibError::new(
+                    ErrorKind::InvalidDatasetOrder,
+                    format!("The dataset id {} is listed twice.", id),
+                ));
+            }
+            indices.push(index);
+        }
+        if indices.len() != self.datasets.len() {
+            return Err(ChartsLibError::new(
+                ErrorKind::InvalidDatasetOrder,
+                format!(
+                    "Got {} dataset ids for {} datasets.",
+                    indices.len(),
+                    self.datasets.len()
+                ),
+            ));
+        }
+        let mut datasets: Vec<Option<Dataset<T>>> = self.datasets.drain(..).map(Some).collect();
+        self.datasets = indices
+            .into_iter()
+            .filter_map(|index| datasets[index].take())
+            .collect();
+        Ok(())
+    }
+
+    pub(crate) fn check_cluster_value_count(
+        &self,
+        values: &[Option<T>],
+    ) -> Result<(), ChartsLibError> {
+        if values.len() != self.datasets.len() {
+            return Err(ChartsLibError::new(
+                ErrorKind::ClusterValueCountMismatch,
+                format!(
+                    "Got {} values for {} datasets.",
+                    values.len(),
+                    self.datasets.len()
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// returns (smallest entry, biggest entry) without missing values, (0.0, 0.0) if there are no entries.
+    /// It is converted to f32 like the values the color rules get.
+    pub fn get_data_range(&self) -> (f32, f32) {
+        let mut entries = self
+            .datasets
+            .iter()
+            .flat_map(|dataset| dataset.data.iter().flatten())
+            .map(|entry| entry.to_f64() as f32);
+        match entries.next() {
+            Some(first) => entries.fold((first, first), |(min, max), entry| {
+                (min.min(entry), max.max(entry))
+            }),
+            None => (0.0, 0.0),
+        }
+    }
+
+    /// Builds the data from raw records. Records with the same `cluster_key` form a cluster,
+    /// records with the same `series_key` a dataset, and all values of the records falling into
+    /// one bar are combined by `aggregation`. Clusters and datasets are in the order their key
+    /// first appears in, dataset ids are assigned in that order starting at 0.
+    /// Bars without records are missing values, or 0 when counting. The values keep their type,
+    /// only the mean is computed in f64, see `Aggregation::apply`.
+    /// returns the data and the cluster names
+    pub fn from_records<R, I, C, S, V>(
+        records: I,
+        cluster_key: C,
+        series_key: S,
+        value: V,
+        aggregation: Aggregation,
+    ) -> (BarChartData<T>, Vec<String>)
+    where
+        I: IntoIterator<Item = R>,
+        C: Fn(&R) -> String,
+        S: Fn(&R) -> String,
+        V: Fn(&R) -> T,
+    {
+        let mut cluster_names: Vec<String> = vec![];
+        let mut cluster_indices: HashMap<String, usize> = HashMap::new();
+        let mut series_names: Vec<String> = vec![];
+        let mut series_indices: HashMap<String, usize> = HashMap::new();
+        // values of every bar, by (series index, cluster index)
+        let mut groups: HashMap<(usize, usize), Vec<T>> = HashMap::new();
+
+        for record in records {
+            let cluster_index = get_or_insert_key(
+                cluster_key(&record),
+                &mut cluster_names,
+                &mut cluster_indices,
+            );
+            let series_index =
+                get_or_insert_key(series_key(&record), &mut series_names, &mut series_indices);
+            groups
+                .entry((series_index, cluster_index))
+                .or_default()
+                .push(value(&record));
+        }
+
+        let datasets = series_names
+            .into_iter()
+            .enumerate()
+            .map(|(series_index, name)| {
+                let data = (0..cluster_names.len())
+                    .map(|cluster_index| {
+                        let values = groups
+                            .get(&(series_index, cluster_index))
+                            .map_or(&[][..], Vec::as_slice);
+                        aggregation.apply(values)
+                    })
+                    .collect();
+                Dataset::with_missing_values(series_index as u32, name, data)
+            })
+            .collect();
+        (BarChartData::new(datasets), cluster_names)
+    }
+}
+
+/// returns the index of the key, new keys are appended
+fn get_or_insert_key(
+    key: String,
+    keys: &mut Vec<String>,
+    indices: &mut HashMap<String, usize>,
+) -> usize {
+    if let Some(index) = indices.get(&key) {
+        return *index;
+    }
+    keys.push(key.clone());
+    indices.insert(key, keys.len() - 1);
+    keys.len() - 1
+}
+
+/// Returns the value to store. Non-finite values are an error, or missing if `lenient`.
+pub fn validate_value<T: ChartValue>(
+    value: Option<T>,
+    dataset_id: u32,
+    cluster_index: usize,
+    lenient: bool,
+) -> Result<Option<T>, ChartsLibError> {
+    match value {
+        Some(value) if !value.is_finite() => {
+            if lenient {
+                Ok(None)
+            } else {
+                Err(non_finite_value_error(value, dataset_id, cluster_index))
+            }
+        }
+        _ => Ok(value),
+    }
+}
+
+fn non_finite_value_error<T: ChartValue>(
+    value: T,
+    dataset_id: u32,
+    cluster_index: usize,
+) -> ChartsLibError {
+    ChartsLibError::new(
+        ErrorKind::NonFiniteValue {
+            dataset_id,
+            cluster_index,
+        },
+        format!(
+            "The value {} of the dataset {} in the cluster {} is not finite.",
+            value, dataset_id, cluster_index
+        ),
+    )
+}
+
+pub(crate) fn empty_data_error() -> ChartsLibError {
+    ChartsLibError::new(
+        ErrorKind::EmptyData,
+        String::from("A chart needs at least one dataset and one cluster."),
+    )
+}
+
+fn duplicate_id_error(id: u32) -> ChartsLibError {
+    ChartsLibError::new(
+        ErrorKind::DuplicateDatasetId { id },
+        format!("The dataset id {} is used more than once.", id),
+    )
+}
+
+fn cluster_index_error(index: usize) -> ChartsLibError {
+    ChartsLibError::new(
+        ErrorKind::ClusterIndexOutOfRange { index },
+        format!("There is no cluster with the index {}.", index),
+    )
+}
+
+fn csv_read_error(error: csv::Error) -> ChartsLibError {
+    ChartsLibError::new(
+        ErrorKind::CsvReadFailed,
+        format!("Could not read the CSV: {}", error),
+    )
 }
@@ -1,66 +1,180 @@
+use crate::charts::animation::{BarAnimation, Easing};
 use crate::charts::axis_enums::{
     AxisOrientation, BarChartDataAxis, BarChartDataPositivity, XAxisOrientation, YAxisOrientation,
 };
-use crate::charts::bar_chart_data::{BarChartData, Dataset};
+use crate::charts::bar_chart_data::{empty_data_error, validate_value, BarChartData, Dataset};
 use crate::charts::bar_chart_style_info_iced::BarChartStyleInfoIced;
+use crate::charts::bar_color_rule::{self, BarColorRule};
+use crate::charts::bar_hit_index::{BarHitIndex, BarHitInfo};
+use crate::charts::bar_selection::BarSelection;
+use crate::charts::chart_spec::{ChartSpec, ChartType};
+use crate::charts::chart_value::ChartValue;
+use crate::charts::cluster_sorting::{sort_clusters, ClusterSorting, SortedClusters};
 use crate::charts::coordinate_system::{
-    AxisAndOrigin, CoordinateSystem, CoordinateSystemConfig, SpaceCalculator,
+    AxisAndOrigin, CoordinateSystem, CoordinateSystemConfig, DrawingAxis, LegendEntry,
+    SpaceCalculator,
 };
 use crate::charts::drawing_helper::BarChartDrawingHelper;
 use crate::charts::error::{ChartsLibError, ErrorKind};
+use crate::charts::fill::Fill;
+use crate::charts::iced_scene_renderer::draw_scene;
+use crate::charts::palette;
+use crate::charts::scene::{CornerRadii, Scene, SceneChart};
+use crate::charts::theme::Theme;
 use crate::charts::util;
-use crate::charts::util::placeholder_get_text_width;
-use crate::{charts, Message};
+use crate::charts::util::{placeholder_get_text_width, rectangle_from_points};
 use iced::canvas::path::Builder;
 use iced::canvas::{LineCap, LineJoin, Path, Stroke};
 use iced::mouse::Interaction;
 use iced::widget::canvas::{Cache, Cursor, Event, Frame, Geometry, Text};
 use iced::{
-    canvas, keyboard, mouse, Canvas, Color, Element, Length, Point, Rectangle, Size, Space,
+    canvas, keyboard, mouse, time, Canvas, Color, Element, Length, Point, Rectangle, Size, Space,
+    Subscription,
 };
 use std::borrow::Borrow;
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
 
+const SELECTION_STROKE_COLOR: Color = Color::BLACK;
+const SELECTION_STROKE_WIDTH: f32 = 2.0;
+const SELECTION_RECTANGLE_COLOR: Color = Color::from_rgba(0.3, 0.5, 0.9, 0.25);
+/// Drags shorter than this (in pixels) are treated as clicks.
+const DRAG_THRESHOLD: f32 = 3.0;
+const CROSSHAIR_COLOR: Color = Color::from_rgb(0.4, 0.4, 0.4);
+const CROSSHAIR_STROKE_WIDTH: f32 = 1.0;
+const CROSSHAIR_HIGHLIGHT_COLOR: Color = Color::from_rgba(0.3, 0.5, 0.9, 0.3);
+const CROSSHAIR_LABEL_BACKGROUND_COLOR: Color = Color::WHITE;
+const HOVER_COLOR: Color = Color::from_rgba(0.0, 0.0, 0.0, 0.05);
+const BAR_OUTLINE_WIDTH: f32 = 1.0;
+const BAR_SHADOW_COLOR: Color = Color::from_rgba(0.0, 0.0, 0.0, 0.15);
+const BAR_SHADOW_OFFSET: (f32, f32) = (2.0, 2.0);
+const MISSING_VALUE_MARKER_COLOR: Color = Color::from_rgb(0.6, 0.6, 0.6);
+const MISSING_VALUE_MARKER_SIZE: f32 = 8.0;
+const MISSING_VALUE_MARKER_STROKE_WIDTH: f32 = 1.5;
+const HOVER_LABEL_BACKGROUND_COLOR: Color = Color::WHITE;
+const HOVER_LABEL_STROKE_COLOR: Color = Color::from_rgb(0.4, 0.4, 0.4);
+const HOVER_LABEL_STROKE_WIDTH: f32 = 1.0;
+/// shown in the hover label of missing values
+const NO_DATA_TEXT: &str = "no data";
+/// Time between two animation frames, about 60 frames per second. For applications driving
+/// several charts with one timer, see `animation_tick`.
+pub const ANIMATION_FRAME_INTERVAL: Duration = Duration::from_millis(16);
+const OTHER_CLUSTER_NAME: &str = "Other";
+/// end of the value axis if all values are 0 or missing, the bar lengths are divided by it
+const EMPTY_VALUE_AXIS_END: f64 = 1.0;
+
+#[derive(Debug, Clone)]
+pub enum BarChartMessage {
+    /// Contains the selected bars as (dataset_id, cluster_index), sorted. The cluster index is
+    /// the index in the data, also if the clusters are shown sorted.
+    SelectionChanged(Vec<(u32, usize)>),
+    /// Emitted by `subscription` while an animation runs, pass it to `update`.
+    AnimationTick(Instant),
+}
+
+/// Everything that depends on the canvas size, recalculated whenever the size changes.
+#[derive(Debug)]
+struct BarChartLayout {
+    size: Size,
+    axis_and_origin: AxisAndOrigin,
+    bar_hit_index: BarHitIndex,
+    /// headline, labels and separator lines
+    background: Scene,
+    /// bars and axis lines
+    data: Scene,
+}
+
+/// `T` is the type of the values of the datasets, see `ChartValue`.
 #[derive(Debug)]
-pub struct BarChartIcedStruct {
+pub struct BarChartIcedStruct<T = f32> {
     coordinate_system: CoordinateSystem,
     data_axis: BarChartDataAxis,
-    pub data: BarChartData,
+    pub data: BarChartData<T>,
     cluster_names: Vec<String>,
+    /// the clusters as they are shown if they are sorted or summed up, see `ClusterSorting`
+    sorted_clusters: Option<SortedClusters<T>>,
     style: BarChartStyleInfoIced,
-    chart_cache: Cache,
-    rectangle_list: Vec<(Rectangle, f32)>,
-    biggest_data_entry_abs: f32,
+    /// headline, labels and separator lines, invalidated by data and layout changes
+    background_cache: Cache,
+    /// bars and axis lines, invalidated by data changes
+    data_cache: Cache,
+    /// hover highlight and label, selection highlight and the selection rectangle
+    overlay_cache: Cache,
+    layout: RefCell<Option<Rc<BarChartLayout>>>,
+    /// (dataset_id, slot) of the hovered bar, see `BarHitInfo`
+    hovered_bar: Option<(u32, usize)>,
+    biggest_data_entry_abs: f64,
+    selection: BarSelection,
+    drag_start: Option<Point>,
+    drag_current: Option<Point>,
+    control_pressed: bool,
+    shift_pressed: bool,
+    animation: Option<BarAnimation>,
 }
 
 //TODO: Keyboard stuff
-impl<'a> canvas::Program<Message> for BarChartIcedStruct {
-    fn update(&mut self, event: Event, bounds: Rectangle<f32>, cursor: Cursor) -> Option<Message> {
+impl<T: ChartValue> canvas::Program<BarChartMessage> for BarChartIcedStruct<T> {
+    fn update(
+        &mut self,
+        event: Event,
+        bounds: Rectangle<f32>,
+        cursor: Cursor,
+    ) -> Option<BarChartMessage> {
         match event {
             Event::Mouse(mouse_event) => match mouse_event {
                 mouse::Event::CursorMoved { .. } => {
                     if let Some(a) = cursor.position_in(&bounds) {
-                        if self.rectangle_list.is_empty() {
-                            self.rectangle_list = self.create_rectangle_list_for_hover(
-                                &AxisAndOrigin::new(
-                                    self.coordinate_system
-                                        .calculate_axis_length_and_origin(bounds.size()),
-                                ),
-                                &mut Frame::new(bounds.size()),
-                                &cursor,
-                                &bounds,
-                            );
+                        let layout = self.get_layout(bounds.size());
+                        if self.drag_start.is_some() {
+                            self.drag_current = Some(a);
+                            self.overlay_cache.clear();
                         }
-                        for rectangle in self.rectangle_list.iter() {
-                            if rectangle.0.contains(a) {
-                                println!("{}", rectangle.1);
-                                self.chart_cache.clear();
-                            }
-                        }
-                        //return Some(Message::NewData);
+                        let hovered_bar = layout.bar_hit_index.get_bar_at(a);
+                        self.set_hovered_bar(
+                            hovered_bar.map(|rectangle| (rectangle.dataset_id, rectangle.slot)),
+                        );
+                    } else {
+                        self.set_hovered_bar(None);
                     };
                     None
                 }
+                mouse::Event::ButtonPressed(mouse::Button::Left) => {
+                    if let Some(position) = cursor.position_in(&bounds) {
+                        self.drag_start = Some(position);
+                        self.drag_current = Some(position);
+                    }
+                    None
+                }
+                mouse::Event::ButtonReleased(mouse::Button::Left) => {
+                    let drag_start = match self.drag_start.take() {
+                        Some(drag_start) => drag_start,
+                        None => return None,
+                    };
+                    let drag_end = self.drag_current.take().unwrap_or(drag_start);
+                    let layout = self.get_layout(bounds.size());
+                    let previous_selection = self.selection.get_selected().clone();
+                    if (drag_end.x - drag_start.x).abs() < DRAG_THRESHOLD
+                        && (drag_end.y - drag_start.y).abs() < DRAG_THRESHOLD
+                    {
+                        self.select_by_click(drag_end, &layout.bar_hit_index);
+                    } else {
+                        self.select_by_rectangle(
+                            rectangle_from_points(drag_start, drag_end),
+                            &layout.bar_hit_index,
+                        );
+                    }
+                    self.overlay_cache.clear();
+                    if *self.selection.get_selected() != previous_selection {
+                        Some(BarChartMessage::SelectionChanged(
+                            self.selection.to_sorted_vec(),
+                        ))
+                    } else {
+                        None
+                    }
+                }
                 _ => None,
             },
             Event::Keyboard(keyboard_event) => match keyboard_event {
@@ -83,34 +197,49 @@ impl<'a> canvas::Program<Message> for BarChartIcedStruct {
                     None
                 }
                 keyboard::Event::ModifiersChanged(modifiers) => {
-                    println!("{:?}", modifiers);
+                    self.control_pressed = modifiers.control || modifiers.logo;
+                    self.shift_pressed = modifiers.shift;
                     None
                 }
             },
         }
     }
     fn draw(&self, bounds: Rectangle<f32>, cursor: Cursor) -> Vec<Geometry> {
-        let chart = self.chart_cache.draw(bounds.size(), |frame| {
-            let cluster_amount =
-                self.data
-                    .get_biggest_amount_of_data_entries_in_one_dataset() as f32;
-            let amount_of_bars_in_per_cluster = self.data.datasets.len() as f32;
-            let axis_and_origin = AxisAndOrigin::new(
-                self.coordinate_system
-                    .calculate_axis_length_and_origin(frame.size()),
-            );
-
-            self.coordinate_system
-                .draw_headline_and_labels(&axis_and_origin, frame);
-            self.draw_bars(&axis_and_origin, frame, &cursor, &bounds);
-            self.coordinate_system.draw_axis(&axis_and_origin, frame);
+        let layout = self.get_layout(bounds.size());
+        let background = self.background_cache.draw(bounds.size(), |frame| {
+            draw_scene(&layout.background, frame);
             frame.fill_text(format!(
                 "X: {:?}\nY:{:?}",
                 self.coordinate_system.x_axis_orientation,
                 self.coordinate_system.y_axis_orientation
             ));
         });
-        vec![chart]
+        let data = self.data_cache.draw(bounds.size(), |frame| {
+            draw_scene(&layout.data, frame);
+        });
+        let overlay = self.overlay_cache.draw(bounds.size(), |frame| {
+            let mut scene = Scene::new(bounds.size());
+            self.draw_hover_and_selection(
+                &layout.bar_hit_index,
+                layout.axis_and_origin.origin,
+                &mut scene,
+            );
+            self.draw_selection_rectangle(&mut scene);
+            draw_scene(&scene, frame);
+        });
+        let mut geometries = vec![background, data, overlay];
+        if self.style.show_crosshair {
+            if let Some(position) = cursor.position_in(&bounds) {
+                if layout.axis_and_origin.get_plot_area().contains(position) {
+                    let mut scene = Scene::new(bounds.size());
+                    self.draw_crosshair(&layout.axis_and_origin, position, &mut scene);
+                    let mut frame = Frame::new(bounds.size());
+                    draw_scene(&scene, &mut frame);
+                    geometries.push(frame.into_geometry());
+                }
+            }
+        }
+        geometries
     }
     fn mouse_interaction(&self, bounds: Rectangle<f32>, cursor: Cursor) -> mouse::Interaction {
         if cursor.is_over(&bounds) {
@@ -121,12 +250,21 @@ impl<'a> canvas::Program<Message> for BarChartIcedStruct {
     }
 }
 
-impl BarChartIcedStruct {
+impl<T: ChartValue> SceneChart for BarChartIcedStruct<T> {
+    fn create_scene(&self, size: Size) -> Scene {
+        let layout = self.calculate_layout(size);
+        let mut scene = layout.background;
+        scene.extend(&layout.data);
+        scene
+    }
+}
+
+impl<T: ChartValue> BarChartIcedStruct<T> {
     pub fn new(
         data_axis: BarChartDataAxis,
         x_axis_orientation: XAxisOrientation,
         y_axis_orientation: YAxisOrientation,
-        data: BarChartData,
+        data: BarChartData<T>,
         cluster_names: Vec<String>,
         style: BarChartStyleInfoIced,
     ) -> Result<Self, ChartsLibError> {
@@ -165,8 +303,17 @@ impl BarChartIcedStruct {
                 ),
             ))
         } else {
+            let mut data = data;
+            data.validate(cluster_names.len(), style.lenient_validation)?;
+            data.replace_non_finite_values();
+            let sorted_clusters = get_sorted_clusters(&data, &cluster_names, &style);
+            let (shown_data, shown_cluster_names) = match &sorted_clusters {
+                Some(sorted_clusters) => (&sorted_clusters.data, &sorted_clusters.cluster_names),
+                None => (&data, &cluster_names),
+            };
             //TODO: create proper coordinate_system_config here
-            let biggest_data_entry_abs = calculate_biggest_data_value(&style, &data);
+            let biggest_data_entry_abs =
+                calculate_biggest_data_value(&style, data_axis, shown_data);
 
             Ok(BarChartIcedStruct {
                 coordinate_system: CoordinateSystem::new(
@@ -176,72 +323,833 @@ impl BarChartIcedStruct {
                         &data_axis,
                         &x_axis_orientation,
                         &y_axis_orientation,
-                        &data,
-                        &cluster_names,
+                        shown_data,
+                        shown_cluster_names,
                         &style,
+                        biggest_data_entry_abs,
                     ),
                 ),
                 data_axis,
                 data,
                 cluster_names,
+                sorted_clusters,
                 style,
-                chart_cache: Default::default(),
-                rectangle_list: vec![],
+                background_cache: Default::default(),
+                data_cache: Default::default(),
+                overlay_cache: Default::default(),
+                layout: RefCell::new(None),
+                hovered_bar: None,
                 biggest_data_entry_abs,
+                selection: BarSelection::new(),
+                drag_start: None,
+                drag_current: None,
+                control_pressed: false,
+                shift_pressed: false,
+                animation: None,
             })
         }
     }
 
-    pub fn view(&mut self) -> Element<Message> {
+    pub fn from_spec(spec: ChartSpec<T>) -> Result<Self, ChartsLibError> {
+        match spec.chart_type {
+            ChartType::Bar => BarChartIcedStruct::new(
+                spec.data_axis,
+                spec.x_axis_orientation,
+                spec.y_axis_orientation,
+                BarChartData::new(spec.datasets),
+                spec.cluster_names,
+                spec.style,
+            ),
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, ChartsLibError> {
+        BarChartIcedStruct::from_spec(ChartSpec::from_json(json)?)
+    }
+
+    pub fn to_spec(&self) -> ChartSpec<T> {
+        ChartSpec {
+            chart_type: ChartType::Bar,
+            data_axis: self.data_axis,
+            x_axis_orientation: self.coordinate_system.x_axis_orientation,
+            y_axis_orientation: self.coordinate_system.y_axis_orientation,
+            datasets: self.data.datasets.clone(),
+            cluster_names: self.cluster_names.clone(),
+            style: self.get_serializable_style(),
+        }
+    }
+
+    /// returns the style without the custom color rules, which can't be serialized
+    fn get_serializable_style(&self) -> BarChartStyleInfoIced {
+        let mut style = self.style.clone();
+        style.bar_color_rules.retain(|rule| !rule.is_custom());
+        style
+    }
+
+    pub fn to_json(&self) -> Result<String, ChartsLibError> {
+        self.to_spec().to_json()
+    }
+
+    pub fn view(&mut self) -> Element<BarChartMessage> {
         Canvas::new(self)
             .width(Length::Fill)
             .height(Length::Fill)
             .into()
     }
 
-    pub fn add_dataset(&mut self, dataset: Dataset) {
+    /// The dataset needs a new id and, unless the validation is lenient, one value per cluster.
+    pub fn add_dataset(&mut self, mut dataset: Dataset<T>) -> Result<(), ChartsLibError> {
+        let lenient = self.style.lenient_validation;
+        self.data
+            .validate_new_dataset(&dataset, self.cluster_names.len(), lenient)?;
+        dataset.replace_non_finite_values();
+        self.start_animation();
         self.data.datasets.push(dataset);
-        self.chart_cache.clear();
-        self.rectangle_list.clear();
+        self.clear_caches();
+        self.invalidate_layout();
         self.recalculate_after_data_changes();
+        Ok(())
     }
 
-    pub fn replace_data(&mut self, data: BarChartData) {
+    /// The data has to match the current cluster names, unless the validation is lenient.
+    pub fn replace_data(&mut self, mut data: BarChartData<T>) -> Result<(), ChartsLibError> {
+        data.validate(self.cluster_names.len(), self.style.lenient_validation)?;
+        data.replace_non_finite_values();
+        self.start_animation();
         self.data = data;
-        self.chart_cache.clear();
-        self.rectangle_list.clear();
+        self.clear_caches();
+        self.invalidate_layout();
         self.recalculate_after_data_changes();
+        Ok(())
     }
 
-    pub fn remove_last_dataset(&mut self) {
+    /// Removing the only dataset is an error, unless the validation is lenient.
+    pub fn remove_last_dataset(&mut self) -> Result<(), ChartsLibError> {
+        self.check_not_emptied(self.data.datasets.len())?;
         if self.data.datasets.is_empty() {
-            return;
+            return Ok(());
         }
+        self.start_animation();
         self.data.datasets.remove(self.data.datasets.len() - 1);
-        self.chart_cache.clear();
-        self.rectangle_list.clear();
+        self.clear_caches();
+        self.invalidate_layout();
+        self.recalculate_after_data_changes();
+        Ok(())
+    }
+
+    /// Removing the only dataset is an error, unless the validation is lenient.
+    pub fn remove_dataset(&mut self, id: u32) -> Result<(), ChartsLibError> {
+        self.check_not_emptied(self.data.datasets.len())?;
+        let data_range = self.get_shown_data().get_data_range();
+        self.data.remove_dataset(id)?;
+        self.start_animation();
+        self.datasets_changed(data_range);
+        Ok(())
+    }
+
+    /// Only redraws the bars, unless the new value changes the value axis or the legend.
+    /// `None` turns the entry into a missing value.
+    pub fn update_value(
+        &mut self,
+        dataset_id: u32,
+        cluster_index: usize,
+        value: Option<T>,
+    ) -> Result<(), ChartsLibError> {
+        let value = validate_value(
+            value,
+            dataset_id,
+            cluster_index,
+            self.style.lenient_validation,
+        )?;
+        let data_range = self.get_shown_data().get_data_range();
+        let old_value = self.data.update_value(dataset_id, cluster_index, value)?;
+        if old_value == value {
+            return Ok(());
+        }
+        self.start_animation();
+        self.data_cache.clear();
+        self.overlay_cache.clear();
+        self.invalidate_layout();
+        // sorted clusters may change their order and with it the labels
+        self.sort_clusters();
+        if self.sorted_clusters.is_some() {
+            self.hovered_bar = None;
+        }
+        if self.is_animating()
+            || self.sorted_clusters.is_some()
+            || self.get_shown_data().get_data_range() != data_range
+            || calculate_biggest_data_value(&self.style, self.data_axis, self.get_shown_data())
+                != self.biggest_data_entry_abs
+        {
+            self.background_cache.clear();
+            self.apply_animation_state();
+        }
+        Ok(())
+    }
+
+    /// Inserts a cluster at `index` with one value per dataset, later clusters move back by one.
+    pub fn insert_cluster(
+        &mut self,
+        index: usize,
+        name: String,
+        values: &[Option<T>],
+    ) -> Result<(), ChartsLibError> {
+        let values = self.validate_cluster_values(values, index)?;
+        self.data.insert_cluster(index, &values)?;
+        let move_back = |(dataset_id, cluster_index): (u32, usize)| {
+            if cluster_index >= index {
+                Some((dataset_id, cluster_index + 1))
+            } else {
+                Some((dataset_id, cluster_index))
+            }
+        };
+        self.start_animation_with(move_back);
+        if self.cluster_names.len() < index {
+            self.cluster_names.resize(index, String::new());
+        }
+        self.cluster_names.insert(index, name);
+        self.selection.remap(move_back);
+        self.hovered_bar = None;
+        self.clear_caches();
+        self.invalidate_layout();
         self.recalculate_after_data_changes();
+        Ok(())
+    }
+
+    /// Removes the cluster at `index` from all datasets, later clusters move forward by one.
+    /// Removing the only cluster is an error, unless the validation is lenient.
+    pub fn remove_cluster(&mut self, index: usize) -> Result<(), ChartsLibError> {
+        self.check_not_emptied(
+            self.data
+                .get_biggest_amount_of_data_entries_in_one_dataset(),
+        )?;
+        self.data.remove_cluster(index)?;
+        let move_forward = |(dataset_id, cluster_index): (u32, usize)| {
+            if cluster_index == index {
+                None
+            } else if cluster_index > index {
+                Some((dataset_id, cluster_index - 1))
+            } else {
+                Some((dataset_id, cluster_index))
+            }
+        };
+        self.start_animation_with(move_forward);
+        if index < self.cluster_names.len() {
+            self.cluster_names.remove(index);
+        }
+        self.selection.remap(move_forward);
+        self.hovered_bar = None;
+        self.clear_caches();
+        self.invalidate_layout();
+        self.recalculate_after_data_changes();
+        Ok(())
+    }
+
+    /// Only the labels change, the bars are kept unless the labels need a different amount of space.
+    pub fn rename_cluster(&mut self, index: usize, name: String) -> Result<(), ChartsLibError> {
+        if index
+            >= self
+                .data
+                .get_biggest_amount_of_data_entries_in_one_dataset()
+                .max(self.cluster_names.len())
+        {
+            return Err(ChartsLibError::new(
+                ErrorKind::ClusterIndexOutOfRange { index },
+                format!("There is no cluster with the index {}.", index),
+            ));
+        }
+        if self.cluster_names.len() <= index {
+            self.cluster_names.resize(index + 1, String::new());
+        }
+        self.cluster_names[index] = name;
+        self.labels_changed();
+        Ok(())
+    }
+
+    /// `ids` has to contain the id of every dataset exactly once.
+    pub fn reorder_datasets(&mut self, ids: &[u32]) -> Result<(), ChartsLibError> {
+        let data_range = self.get_shown_data().get_data_range();
+        self.data.reorder_datasets(ids)?;
+        self.start_animation();
+        self.datasets_changed(data_range);
+        Ok(())
+    }
+
+    /// Redraws the bars after datasets were removed or reordered. The labels and the legend are
+    /// only redrawn if the value axis, the shown legend or the space of the axes changed, like
+    /// in `update_value`. `data_range` is the range before the change.
+    fn datasets_changed(&mut self, data_range: (f32, f32)) {
+        let old_layout = self
+            .layout
+            .get_mut()
+            .as_ref()
+            .map(|layout| (layout.size, layout.axis_and_origin.get_axis_and_origin()));
+        let biggest_data_entry_abs = self.biggest_data_entry_abs;
+        let config = &self.coordinate_system.coordinate_system_config;
+        let legend_entries = config
+            .legend_alignment
+            .map(|_| config.legend_entries.clone());
+
+        self.data_cache.clear();
+        self.overlay_cache.clear();
+        self.invalidate_layout();
+        self.recalculate_after_data_changes();
+
+        let config = &self.coordinate_system.coordinate_system_config;
+        let background_unchanged = match old_layout {
+            Some((size, old_axis_and_origin)) => {
+                !self.is_animating()
+                    && self.sorted_clusters.is_none()
+                    && !self.data.datasets.is_empty()
+                    && self.get_shown_data().get_data_range() == data_range
+                    && self.biggest_data_entry_abs == biggest_data_entry_abs
+                    && config.legend_alignment.map(|_| &config.legend_entries)
+                        == legend_entries.as_ref()
+                    && self
+                        .coordinate_system
+                        .calculate_axis_length_and_origin(size)
+                        == old_axis_and_origin
+            }
+            None => false,
+        };
+        if !background_unchanged {
+            self.background_cache.clear();
+        }
+    }
+
+    /// Dataset names are only shown in the legend.
+    pub fn set_dataset_name(&mut self, id: u32, name: String) -> Result<(), ChartsLibError> {
+        self.data.set_dataset_name(id, name)?;
+        self.labels_changed();
+        Ok(())
+    }
+
+    /// Appends a cluster named `name` with one value per dataset, for live data. If `max_clusters`
+    /// is set, the oldest clusters are dropped so it works as a rolling window. The cluster is
+    /// appended behind all cluster names, names without values become missing values.
+    /// The data is changed in place and the selection moves along with its bars. If the amount of
+    /// clusters, the value axis and the space for the labels stay the same, the laid out bars move
+    /// forward and only the new cluster is laid out, otherwise the whole chart is laid out again.
+    pub fn push_cluster(
+        &mut self,
+        name: String,
+        values: &[Option<T>],
+        max_clusters: Option<usize>,
+    ) -> Result<(), ChartsLibError> {
+        let cluster_amount = self
+            .data
+            .get_biggest_amount_of_data_entries_in_one_dataset()
+            .max(self.cluster_names.len());
+        let values = self.validate_cluster_values(values, cluster_amount)?;
+        let data_range = self.get_shown_data().get_data_range();
+        let biggest_data_entry_abs = self.biggest_data_entry_abs;
+        let cluster_names = self.get_shown_cluster_names().to_vec();
+        self.data.pad_clusters(cluster_amount);
+        // the cached layout still shows the bars before the change, so animating can start afterwards
+        let dropped = self.data.push_cluster(&values, max_clusters)?;
+        let shift_cluster = |(dataset_id, cluster_index): (u32, usize)| {
+            cluster_index
+                .checked_sub(dropped)
+                .map(|cluster_index| (dataset_id, cluster_index))
+        };
+        self.start_animation_with(shift_cluster);
+
+        self.cluster_names.resize(cluster_amount, String::new());
+        self.cluster_names.push(name);
+        self.cluster_names
+            .drain(..dropped.min(self.cluster_names.len()));
+        self.selection.remap(shift_cluster);
+        self.hovered_bar = None;
+
+        self.recalculate_after_data_changes();
+        if self.can_shift_layout(dropped, data_range, biggest_data_entry_abs) {
+            self.shift_layout(dropped);
+            if self.get_shown_cluster_names() != cluster_names.as_slice() {
+                self.background_cache.clear();
+            }
+            self.data_cache.clear();
+            self.overlay_cache.clear();
+        } else {
+            self.clear_caches();
+            self.invalidate_layout();
+        }
+        Ok(())
+    }
+
+    /// Whether the cached layout can be reused after `dropped` clusters were dropped and as many
+    /// appended, see `push_cluster`. Sorted clusters and animations always need a new layout.
+    fn can_shift_layout(
+        &mut self,
+        dropped: usize,
+        data_range: (f32, f32),
+        biggest_data_entry_abs: f64,
+    ) -> bool {
+        let slot_amount = self
+            .get_shown_data()
+            .get_biggest_amount_of_data_entries_in_one_dataset();
+        if dropped == 0
+            || self.is_animating()
+            || self.sorted_clusters.is_some()
+            || self.get_shown_data().get_data_range() != data_range
+            || self.biggest_data_entry_abs != biggest_data_entry_abs
+        {
+            return false;
+        }
+        match self.layout.get_mut().as_ref() {
+            Some(layout) => {
+                layout.bar_hit_index.get_slot_amount() == slot_amount
+                    && self
+                        .coordinate_system
+                        .calculate_axis_length_and_origin(layout.size)
+                        == layout.axis_and_origin.get_axis_and_origin()
+            }
+            None => false,
+        }
+    }
+
+    /// Moves the bars of the cached layout forward by `dropped` slots and only lays out the
+    /// clusters appended behind them. The layout is recalculated on the next draw if it is shared.
+    fn shift_layout(&mut self, dropped: usize) {
+        let layout = match self.layout.get_mut().take().map(Rc::try_unwrap) {
+            Some(Ok(layout)) => layout,
+            _ => return,
+        };
+        let slot_amount = layout.bar_hit_index.get_slot_amount();
+        let appended = self.calculate_bar_hit_index(
+            &layout.axis_and_origin,
+            slot_amount.saturating_sub(dropped)..slot_amount,
+        );
+        let mut bar_hit_index = layout.bar_hit_index;
+        bar_hit_index.shift_slots(dropped, appended);
+        let background = self.create_background_scene(layout.size, &layout.axis_and_origin);
+        let data = self.create_data_scene(layout.size, &layout.axis_and_origin, &bar_hit_index);
+        *self.layout.get_mut() = Some(Rc::new(BarChartLayout {
+            size: layout.size,
+            axis_and_origin: layout.axis_and_origin,
+            bar_hit_index,
+            background,
+            data,
+        }));
+    }
+
+    /// returns the values of a new cluster at `cluster_index`, see `validate_value`
+    fn validate_cluster_values(
+        &self,
+        values: &[Option<T>],
+        cluster_index: usize,
+    ) -> Result<Vec<Option<T>>, ChartsLibError> {
+        self.data.check_cluster_value_count(values)?;
+        values
+            .iter()
+            .zip(&self.data.datasets)
+            .map(|(value, dataset)| {
+                validate_value(
+                    *value,
+                    dataset.id,
+                    cluster_index,
+                    self.style.lenient_validation,
+                )
+            })
+            .collect()
+    }
+
+    /// errors if removing one of `amount` datasets or clusters leaves the chart empty
+    fn check_not_emptied(&self, amount: usize) -> Result<(), ChartsLibError> {
+        if amount <= 1 && !self.style.lenient_validation {
+            Err(empty_data_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Ticks while an animation runs, the messages have to be passed to `update`.
+    pub fn subscription(&self) -> Subscription<BarChartMessage> {
+        if self.is_animating() {
+            time::every(ANIMATION_FRAME_INTERVAL).map(BarChartMessage::AnimationTick)
+        } else {
+            Subscription::none()
+        }
+    }
+
+    pub fn update(&mut self, message: &BarChartMessage) {
+        if let BarChartMessage::AnimationTick(now) = message {
+            self.animation_tick(*now);
+        }
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.animation.is_some()
+    }
+
+    /// advances a running animation to `now`
+    pub fn animation_tick(&mut self, now: Instant) {
+        let finished = match &mut self.animation {
+            Some(animation) => animation.advance(now),
+            None => return,
+        };
+        if finished {
+            self.animation = None;
+        }
+        self.apply_animation_state();
+        self.clear_caches();
+        self.invalidate_layout();
+    }
+
+    /// Remembers the currently shown bars as the start of an animation, to be called before the data changes.
+    /// Nothing is animated if animations are disabled or the chart wasn't laid out yet.
+    fn start_animation(&mut self) {
+        self.start_animation_with(Some);
+    }
+
+    /// `start_animation` for changes that move bars to other clusters or datasets, `remap`
+    /// returns where a bar is after the change, `None` if it is removed. The bars of the
+    /// other cluster stay in the other cluster, see `ClusterSorting`.
+    fn start_animation_with<F>(&mut self, remap: F)
+    where
+        F: Fn((u32, usize)) -> Option<(u32, usize)>,
+    {
+        let duration = match self.style.animation_duration_ms {
+            Some(duration) if duration > 0 => Duration::from_millis(duration),
+            _ => {
+                self.animation = None;
+                return;
+            }
+        };
+        let easing = self.style.animation_easing.unwrap_or(Easing::EaseOut);
+        let from_biggest_value = self.biggest_data_entry_abs;
+        self.animation = self.layout.get_mut().as_ref().map(|layout| {
+            let from_bars = layout
+                .bar_hit_index
+                .get_bars()
+                .iter()
+                .filter_map(|bar| {
+                    let key = match bar.cluster_index {
+                        Some(cluster_index) => {
+                            let (dataset_id, cluster_index) =
+                                remap((bar.dataset_id, cluster_index))?;
+                            (dataset_id, Some(cluster_index))
+                        }
+                        None => (bar.dataset_id, None),
+                    };
+                    Some((key, bar.bar))
+                })
+                .collect();
+            BarAnimation::new(duration, easing, from_bars, from_biggest_value)
+        });
+    }
+
+    /// scales the value axis to the current state of the animation, or to the data if there is none
+    fn apply_animation_state(&mut self) {
+        let biggest_data_entry_abs =
+            calculate_biggest_data_value(&self.style, self.data_axis, self.get_shown_data());
+        self.biggest_data_entry_abs = match &self.animation {
+            Some(animation) => animation.interpolate_biggest_value(biggest_data_entry_abs),
+            None => biggest_data_entry_abs,
+        };
+        self.coordinate_system.coordinate_system_config = update_config(
+            &self.data_axis,
+            &self.coordinate_system.x_axis_orientation,
+            &self.coordinate_system.y_axis_orientation,
+            self.get_shown_data(),
+            self.get_shown_cluster_names(),
+            &self.style,
+            self.biggest_data_entry_abs,
+        );
+    }
+
+    /// moves the bars from where they were before the data changed towards their new place
+    fn animate_bars(&self, bar_hit_index: &mut BarHitIndex, origin: Point) {
+        if let Some(animation) = &self.animation {
+            for bar in bar_hit_index.get_bars_mut() {
+                let collapsed = match self.data_axis {
+                    BarChartDataAxis::XPositive | BarChartDataAxis::XNegative => Rectangle::new(
+                        Point::new(bar.bar.x, origin.y),
+                        Size::new(bar.bar.width, 0.0),
+                    ),
+                    BarChartDataAxis::YPositive | BarChartDataAxis::YNegative => Rectangle::new(
+                        Point::new(origin.x, bar.bar.y),
+                        Size::new(0.0, bar.bar.height),
+                    ),
+                };
+                bar.bar = animation.interpolate_bar(
+                    bar.dataset_id,
+                    bar.cluster_index,
+                    bar.bar,
+                    collapsed,
+                );
+            }
+        }
+    }
+
+    /// Returns the value of the bar, `None` if it is missing or doesn't exist. The cluster index
+    /// is the index in the data, like in the selection, also if the clusters are shown sorted.
+    pub fn get_value(&self, dataset_id: u32, cluster_index: usize) -> Option<T> {
+        self.data
+            .datasets
+            .iter()
+            .find(|dataset| dataset.id == dataset_id)?
+            .get_value(cluster_index)
+    }
+
+    /// `get_value` of the cluster shown in the slot, the sum for the other cluster
+    fn get_shown_value(&self, dataset_id: u32, slot: usize) -> Option<T> {
+        self.get_shown_data()
+            .datasets
+            .iter()
+            .find(|dataset| dataset.id == dataset_id)?
+            .get_value(slot)
+    }
+
+    /// returns the slot showing the cluster of the data, see `SortedClusters`
+    fn get_slot(&self, cluster_index: usize) -> Option<usize> {
+        match &self.sorted_clusters {
+            Some(sorted_clusters) => sorted_clusters.get_slot(cluster_index),
+            None => Some(cluster_index),
+        }
+    }
+
+    /// returns the cluster of the data shown in the slot, `None` for the other cluster
+    fn get_cluster_index(&self, slot: usize) -> Option<usize> {
+        match &self.sorted_clusters {
+            Some(sorted_clusters) => sorted_clusters.get_cluster_index(slot),
+            None => Some(slot),
+        }
+    }
+
+    /// the data as it is laid out, sorted and summed up if set in the style
+    fn get_shown_data(&self) -> &BarChartData<T> {
+        match &self.sorted_clusters {
+            Some(sorted_clusters) => &sorted_clusters.data,
+            None => &self.data,
+        }
+    }
+
+    fn get_shown_cluster_names(&self) -> &[String] {
+        match &self.sorted_clusters {
+            Some(sorted_clusters) => &sorted_clusters.cluster_names,
+            None => &self.cluster_names,
+        }
+    }
+
+    fn sort_clusters(&mut self) {
+        self.sorted_clusters = get_sorted_clusters(&self.data, &self.cluster_names, &self.style);
+    }
+
+    /// Shows the clusters sorted and only the first `max_shown_clusters` of them, the others summed
+    /// up into one cluster. The data keeps its order and so do the cluster indices of the selection.
+    /// Selected bars summed up into the other cluster highlight its bar.
+    pub fn set_cluster_sorting(
+        &mut self,
+        sorting: Option<ClusterSorting>,
+        max_shown_clusters: Option<usize>,
+    ) {
+        self.style.cluster_sorting = sorting;
+        self.style.max_shown_clusters = max_shown_clusters;
+        self.animation = None;
+        self.hovered_bar = None;
+        self.clear_caches();
+        self.invalidate_layout();
+        self.recalculate_after_data_changes();
+    }
+
+    /// returns the selected bars as (dataset_id, cluster_index), see `get_value`
+    pub fn get_selection(&self) -> &HashSet<(u32, usize)> {
+        self.selection.get_selected()
+    }
+
+    pub fn set_selection(&mut self, selection: HashSet<(u32, usize)>) {
+        self.selection.replace(selection);
+        self.remove_invalid_selection_entries();
+        self.overlay_cache.clear();
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selection.clear();
+        self.overlay_cache.clear();
+    }
+
+    fn clear_caches(&mut self) {
+        self.background_cache.clear();
+        self.data_cache.clear();
+        self.overlay_cache.clear();
+    }
+
+    fn set_hovered_bar(&mut self, hovered_bar: Option<(u32, usize)>) {
+        if self.hovered_bar != hovered_bar {
+            self.hovered_bar = hovered_bar;
+            self.overlay_cache.clear();
+        }
+    }
+
+    /// Returns the color of the first matching color rule, otherwise the dataset fill.
+    /// The rules get the index past the last cluster for the other cluster.
+    fn get_bar_fill(&self, bar: &BarHitInfo, data_range: (f32, f32)) -> Fill {
+        let rule_color = bar.value.and_then(|value| {
+            bar_color_rule::get_rule_color(
+                &self.style.bar_color_rules,
+                bar.dataset_id,
+                bar.cluster_index.unwrap_or_else(|| {
+                    self.data
+                        .get_biggest_amount_of_data_entries_in_one_dataset()
+                }),
+                value,
+                data_range,
+            )
+        });
+        match rule_color {
+            Some(color) => Fill::Solid(color),
+            None => get_dataset_fill(&self.style, bar.dataset_id),
+        }
+    }
+
+    /// The bars of the other cluster can't be selected, they stand for several clusters.
+    fn select_by_click(&mut self, position: Point, bar_hit_index: &BarHitIndex) {
+        let clicked_bar = bar_hit_index
+            .get_bar_at(position)
+            .and_then(get_selectable_bar);
+
+        match clicked_bar {
+            Some(bar) => {
+                if self.shift_pressed {
+                    let bars_in_drawing_order: Vec<(u32, usize)> = bar_hit_index
+                        .get_bars()
+                        .iter()
+                        .filter_map(get_selectable_bar)
+                        .collect();
+                    self.selection.select_range(bar, &bars_in_drawing_order);
+                } else if self.control_pressed {
+                    self.selection.toggle(bar);
+                } else {
+                    self.selection.select_single(bar);
+                }
+            }
+            None => {
+                if !self.shift_pressed && !self.control_pressed {
+                    self.selection.clear();
+                }
+            }
+        }
+    }
+
+    fn select_by_rectangle(&mut self, selection_rectangle: Rectangle, bar_hit_index: &BarHitIndex) {
+        let bars: Vec<(u32, usize)> = bar_hit_index
+            .get_bars_in_rectangle(&selection_rectangle)
+            .into_iter()
+            .filter_map(get_selectable_bar)
+            .collect();
+        self.selection
+            .select_all(&bars, self.shift_pressed || self.control_pressed);
+    }
+
+    fn remove_invalid_selection_entries(&mut self) {
+        let datasets = &self.data.datasets;
+        self.selection.retain(|(dataset_id, cluster_index)| {
+            datasets.iter().any(|dataset| {
+                dataset.id == *dataset_id && dataset.get_value(*cluster_index).is_some()
+            })
+        });
+    }
+
+    /// returns the cached layout, recalculated if the size changed since the last call
+    fn get_layout(&self, size: Size) -> Rc<BarChartLayout> {
+        let mut layout = self.layout.borrow_mut();
+        match layout.as_ref() {
+            Some(cached_layout) if cached_layout.size == size => Rc::clone(cached_layout),
+            _ => {
+                let new_layout = Rc::new(self.calculate_layout(size));
+                *layout = Some(Rc::clone(&new_layout));
+                new_layout
+            }
+        }
+    }
+
+    fn calculate_layout(&self, size: Size) -> BarChartLayout {
+        let axis_and_origin = AxisAndOrigin::new(
+            self.coordinate_system
+                .calculate_axis_length_and_origin(size),
+        );
+        let slot_amount = self
+            .get_shown_data()
+            .get_biggest_amount_of_data_entries_in_one_dataset();
+        let mut bar_hit_index = self.calculate_bar_hit_index(&axis_and_origin, 0..slot_amount);
+        self.animate_bars(&mut bar_hit_index, axis_and_origin.origin);
+        let background = self.create_background_scene(size, &axis_and_origin);
+        let data = self.create_data_scene(size, &axis_and_origin, &bar_hit_index);
+
+        BarChartLayout {
+            size,
+            axis_and_origin,
+            bar_hit_index,
+            background,
+            data,
+        }
+    }
+
+    /// headline, labels and legend
+    fn create_background_scene(&self, size: Size, axis_and_origin: &AxisAndOrigin) -> Scene {
+        let mut background = Scene::new(size);
+        if let Some(background_color) = self.style.background_color {
+            background.fill_rectangle(Point::ORIGIN, size, background_color);
+        }
+        self.coordinate_system
+            .draw_headline_and_labels(axis_and_origin, &mut background);
+        background
+    }
+
+    /// bars and axis lines
+    fn create_data_scene(
+        &self,
+        size: Size,
+        axis_and_origin: &AxisAndOrigin,
+        bar_hit_index: &BarHitIndex,
+    ) -> Scene {
+        let mut data = Scene::new(size);
+        self.draw_bars(bar_hit_index, axis_and_origin.origin, &mut data);
+        self.coordinate_system.draw_axis(axis_and_origin, &mut data);
+        data
+    }
+
+    /// Recreates the labels and the legend. The bars only need to be redrawn if the axes moved.
+    fn labels_changed(&mut self) {
+        let axis_and_origin = self
+            .layout
+            .get_mut()
+            .as_ref()
+            .map(|layout| (layout.size, layout.axis_and_origin.get_axis_and_origin()));
+        self.sort_clusters();
+        self.apply_animation_state();
+        self.background_cache.clear();
+        if let Some((size, old_axis_and_origin)) = axis_and_origin {
+            if self
+                .coordinate_system
+                .calculate_axis_length_and_origin(size)
+                == old_axis_and_origin
+            {
+                self.invalidate_layout();
+                return;
+            }
+        }
+        self.data_cache.clear();
+        self.overlay_cache.clear();
+        self.invalidate_layout();
+    }
+
+    fn invalidate_layout(&mut self) {
+        *self.layout.get_mut() = None;
     }
 
     fn recalculate_after_data_changes(&mut self) {
+        self.sort_clusters();
+        self.remove_invalid_selection_entries();
         if !self.data.datasets.is_empty() {
-            self.coordinate_system.coordinate_system_config = update_config(
-                &self.data_axis,
-                &self.coordinate_system.x_axis_orientation,
-                &self.coordinate_system.y_axis_orientation,
-                &self.data,
-                &self.cluster_names,
-                &self.style,
-            );
-            self.biggest_data_entry_abs = calculate_biggest_data_value(&self.style, &self.data);
+            self.apply_animation_state();
         }
     }
 
     fn calculate_bar_width_and_cluster_spacing(&self, axis_length: f32) -> (f32, f32) {
-        let cluster_amount =
-            self.data
-                .get_biggest_amount_of_data_entries_in_one_dataset() as f32;
-        let amount_of_bars_in_per_cluster = self.data.datasets.len() as f32;
+        let data = self.get_shown_data();
+        let cluster_amount = data.get_biggest_amount_of_data_entries_in_one_dataset() as f32;
+        let amount_of_bars_in_per_cluster = data.datasets.len() as f32;
         let mut cluster_spacing = self.style.min_cluster_spacing;
 
         let space_in_cluster_without_spacing =
@@ -261,33 +1169,115 @@ impl BarChartIcedStruct {
         (bar_width, cluster_spacing)
     }
 
-    fn create_drawable_bar_vec(&self) -> Vec<Vec<(u32, f32)>> {
-        let cluster_amount = self
-            .data
-            .get_biggest_amount_of_data_entries_in_one_dataset();
+    /// returns the bars of the slots, entries beyond the end of a dataset are missing values
+    fn create_drawable_bar_vec(&self, slots: Range<usize>) -> Vec<Vec<(u32, Option<f64>)>> {
+        let data = self.get_shown_data();
+        slots
+            .map(|slot| {
+                data.datasets
+                    .iter()
+                    .map(|dataset| (dataset.id, dataset.get_value(slot).map(ChartValue::to_f64)))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// draws all shadows first, so no shadow covers a neighbouring bar
+    fn draw_bars(&self, bar_hit_index: &BarHitIndex, origin: Point, scene: &mut Scene) {
+        if self.style.show_bar_shadow {
+            let (offset_x, offset_y) = self.style.bar_shadow_offset.unwrap_or(BAR_SHADOW_OFFSET);
+            let shadow_fill = Fill::Solid(self.style.bar_shadow_color.unwrap_or(BAR_SHADOW_COLOR));
+            for bar in bar_hit_index
+                .get_bars()
+                .iter()
+                .filter(|bar| bar.value.is_some())
+            {
+                scene.fill_rounded_rectangle(
+                    Point::new(bar.bar.x + offset_x, bar.bar.y + offset_y),
+                    bar.bar.size(),
+                    self.get_bar_corner_radii(&bar.bar, origin),
+                    &shadow_fill,
+                );
+            }
+        }
+        let data_range = self.get_shown_data().get_data_range();
+        for bar in bar_hit_index.get_bars() {
+            if bar.value.is_some() {
+                self.draw_bar(bar.bar, origin, &self.get_bar_fill(bar, data_range), scene);
+            } else if self.style.show_missing_value_marker {
+                self.draw_missing_value_marker(bar.bar, scene);
+            }
+        }
+    }
+
+    /// draws a cross on the axis, in the middle of the place of the bar
+    fn draw_missing_value_marker(&self, bar: Rectangle, scene: &mut Scene) {
+        let half_size = bar.width.max(bar.height).min(MISSING_VALUE_MARKER_SIZE) / 2.0;
+        let center = Point::new(bar.x + bar.width / 2.0, bar.y + bar.height / 2.0);
+        let color = self
+            .style
+            .missing_value_marker_color
+            .unwrap_or(MISSING_VALUE_MARKER_COLOR);
+        scene.stroke_line(
+            Point::new(center.x - half_size, center.y - half_size),
+            Point::new(center.x + half_size, center.y + half_size),
+            color,
+            MISSING_VALUE_MARKER_STROKE_WIDTH,
+        );
+        scene.stroke_line(
+            Point::new(center.x - half_size, center.y + half_size),
+            Point::new(center.x + half_size, center.y - half_size),
+            color,
+            MISSING_VALUE_MARKER_STROKE_WIDTH,
+        );
+    }
 
-        let mut data_vector: Vec<Vec<(u32, f32)>> =
-            vec![vec![(0, 0.0); self.data.datasets.len()]; cluster_amount];
+    fn draw_bar(&self, bar: Rectangle, origin: Point, fill: &Fill, scene: &mut Scene) {
+        let radii = self.get_bar_corner_radii(&bar, origin);
+        scene.fill_rounded_rectangle(Point::new(bar.x, bar.y), bar.size(), radii, fill);
+        if let Some(outline_color) = self.style.bar_outline_color {
+            scene.stroke_rounded_rectangle(
+                Point::new(bar.x, bar.y),
+                bar.size(),
+                radii,
+                outline_color,
+                self.style.bar_outline_width.unwrap_or(BAR_OUTLINE_WIDTH),
+            );
+        }
+    }
 
-        for (dataset_index, dataset) in self.data.datasets.iter().enumerate() {
-            for cluster_index in 0..cluster_amount {
-                if let Some(entry) = dataset.data.get(cluster_index) {
-                    data_vector.get_mut(cluster_index).unwrap()[dataset_index].1 = *entry;
+    /// Only the end of the bar away from the axis is rounded. Which end that is follows from
+    /// the side of the axis the bar lies on, so negative values are rounded at the other end.
+    fn get_bar_corner_radii(&self, bar: &Rectangle, origin: Point) -> CornerRadii {
+        let radius = match self.style.bar_corner_radius {
+            Some(radius) if radius > 0.0 => radius,
+            _ => return CornerRadii::zero(),
+        };
+        match self.data_axis {
+            BarChartDataAxis::XPositive | BarChartDataAxis::XNegative => {
+                if bar.y + bar.height / 2.0 < origin.y {
+                    CornerRadii::new(radius, radius, 0.0, 0.0)
+                } else {
+                    CornerRadii::new(0.0, 0.0, radius, radius)
+                }
+            }
+            BarChartDataAxis::YPositive | BarChartDataAxis::YNegative => {
+                if bar.x + bar.width / 2.0 > origin.x {
+                    CornerRadii::new(0.0, radius, radius, 0.0)
+                } else {
+                    CornerRadii::new(radius, 0.0, 0.0, radius)
                 }
-                data_vector[cluster_index][dataset_index].0 = dataset.id;
             }
         }
-        data_vector
     }
 
-    fn draw_bars(
+    /// Lays out the bars of the slots, the index covers all slots of the shown data.
+    fn calculate_bar_hit_index(
         &self,
         axis_and_origin: &AxisAndOrigin,
-        frame: &mut Frame,
-        cursor: &Cursor,
-        bounds: &Rectangle,
-    ) -> Vec<(Rectangle, f32)> {
-        let mut rectangle_list_to_return: Vec<(Rectangle, f32)> = vec![];
+        slots: Range<usize>,
+    ) -> BarHitIndex {
+        let mut bars: Vec<BarHitInfo> = vec![];
 
         let (
             x_axis_positive_length,
@@ -297,27 +1287,24 @@ impl BarChartIcedStruct {
             origin,
         ) = axis_and_origin.get_axis_and_origin();
 
-        let data_vector = self.create_drawable_bar_vec();
+        let slot_amount = self
+            .get_shown_data()
+            .get_biggest_amount_of_data_entries_in_one_dataset();
+        let data_vector = self.create_drawable_bar_vec(slots.clone());
 
-        let bar_spacing = if self.data.datasets.len() == 1 {
+        let bar_spacing = if self.get_shown_data().datasets.len() == 1 {
             0.0
         } else {
             self.style.bar_spacing
         };
-        let (bar_width, cluster_spacing) = match self.data_axis {
-            BarChartDataAxis::XPositive => {
-                self.calculate_bar_width_and_cluster_spacing(x_axis_positive_length)
-            }
-            BarChartDataAxis::XNegative => {
-                self.calculate_bar_width_and_cluster_spacing(x_axis_negative_length)
-            }
-            BarChartDataAxis::YPositive => {
-                self.calculate_bar_width_and_cluster_spacing(y_axis_positive_length)
-            }
-            BarChartDataAxis::YNegative => {
-                self.calculate_bar_width_and_cluster_spacing(y_axis_negative_length)
-            }
+        let cluster_axis_length = match self.data_axis {
+            BarChartDataAxis::XPositive => x_axis_positive_length,
+            BarChartDataAxis::XNegative => x_axis_negative_length,
+            BarChartDataAxis::YPositive => y_axis_positive_length,
+            BarChartDataAxis::YNegative => y_axis_negative_length,
         };
+        let (bar_width, cluster_spacing) =
+            self.calculate_bar_width_and_cluster_spacing(cluster_axis_length);
 
         let factor = 1.0 / self.biggest_data_entry_abs;
         let indicator_axis_length: f32;
@@ -449,7 +1436,17 @@ impl BarChartIcedStruct {
             (_, _, _) => panic!("Bar Graph draw bars died"),
         }
 
-        for cluster in &data_vector {
+        let cluster_step = orientation_factor * cluster_axis_length / slot_amount as f32;
+        if self.data_axis == BarChartDataAxis::XPositive
+            || self.data_axis == BarChartDataAxis::XNegative
+        {
+            current_position.x += slots.start as f32 * cluster_step;
+        } else {
+            current_position.y += slots.start as f32 * cluster_step;
+        }
+
+        for (slot, cluster) in slots.zip(&data_vector) {
+            let cluster_index = self.get_cluster_index(slot);
             if self.data_axis == BarChartDataAxis::XPositive
                 || self.data_axis == BarChartDataAxis::XNegative
             {
@@ -459,7 +1456,8 @@ impl BarChartIcedStruct {
             }
 
             for entry in cluster {
-                let mut bar_length = indicator_axis_length * entry.1 * factor;
+                let mut bar_length =
+                    (indicator_axis_length as f64 * entry.1.unwrap_or(0.0) * factor) as f32;
                 let mut is_entry_negative = false;
                 if bar_length < 0.0 {
                     bar_length *= -1.0;
@@ -472,13 +1470,6 @@ impl BarChartIcedStruct {
                         draw_from_left = !draw_from_left;
                     }
                 }
-                let color = match self.style.color_map.get(&entry.0) {
-                    Some(temp) => *temp,
-                    None => {
-                        self.style.standard_color_map
-                            [entry.0 as usize % self.style.standard_color_map.len()]
-                    }
-                };
                 let size = if self.data_axis == BarChartDataAxis::XPositive
                     || self.data_axis == BarChartDataAxis::XNegative
                 {
@@ -499,19 +1490,19 @@ impl BarChartIcedStruct {
                     || (is_entry_negative, positivity) == (false, BarChartDataPositivity::Both)
                     || bar_length == 0.0
                 {
-                    frame.fill(
-                        &Path::rectangle(Point::new(current_position.x, current_position.y), size),
-                        color,
-                    );
-                    rectangle_list_to_return.push((
-                        self.create_rectangle_for_list(
+                    bars.push(BarHitInfo {
+                        bar: Rectangle::new(current_position, size),
+                        hover_area: self.create_rectangle_for_list(
                             current_position,
                             indicator_axis_length,
                             origin,
                             bar_width,
                         ),
-                        entry.1,
-                    ));
+                        dataset_id: entry.0,
+                        slot,
+                        cluster_index,
+                        value: entry.1.map(|value| value as f32),
+                    });
                 }
 
                 if draw_from_top {
@@ -547,7 +1538,21 @@ impl BarChartIcedStruct {
                 current_position.y += (cluster_spacing - bar_spacing) * orientation_factor;
             }
         }
-        rectangle_list_to_return
+
+        let (cluster_axis, cluster_axis_start) = if self.data_axis == BarChartDataAxis::XPositive
+            || self.data_axis == BarChartDataAxis::XNegative
+        {
+            (DrawingAxis::X, origin.x)
+        } else {
+            (DrawingAxis::Y, origin.y)
+        };
+        BarHitIndex::from_bars(
+            bars,
+            slot_amount,
+            cluster_axis,
+            cluster_axis_start,
+            cluster_step,
+        )
     }
 
     fn create_rectangle_for_list(
@@ -583,28 +1588,320 @@ impl BarChartIcedStruct {
         Rectangle::new(point_for_rectangle_list, size)
     }
 
-    fn create_rectangle_list_for_hover(
+    fn draw_hover_and_selection(
         &self,
-        axis_and_origin: &AxisAndOrigin,
-        frame: &mut Frame,
-        cursor: &Cursor,
-        bounds: &Rectangle,
-    ) -> Vec<(Rectangle, f32)> {
-        self.draw_bars(&axis_and_origin, frame, cursor, bounds)
+        bar_hit_index: &BarHitIndex,
+        origin: Point,
+        scene: &mut Scene,
+    ) {
+        let hovered_bar = self
+            .hovered_bar
+            .and_then(|(dataset_id, slot)| bar_hit_index.get_bar(dataset_id, slot));
+        if let Some(rectangle) = hovered_bar {
+            scene.fill_rectangle(
+                Point::new(rectangle.hover_area.x, rectangle.hover_area.y),
+                rectangle.hover_area.size(),
+                self.style.hover_color.unwrap_or(HOVER_COLOR),
+            );
+        }
+        let data_range = self.get_shown_data().get_data_range();
+        // selected clusters summed up into the other cluster share its bar
+        let selected_bars: HashSet<(u32, usize)> = self
+            .selection
+            .get_selected()
+            .iter()
+            .filter_map(|(dataset_id, cluster_index)| {
+                Some((*dataset_id, self.get_slot(*cluster_index)?))
+            })
+            .collect();
+        for (dataset_id, slot) in selected_bars {
+            if let Some(rectangle) = bar_hit_index.get_bar(dataset_id, slot) {
+                self.draw_selected_bar(
+                    rectangle.bar,
+                    origin,
+                    self.get_bar_fill(rectangle, data_range),
+                    scene,
+                );
+            }
+        }
+        if let Some(rectangle) = hovered_bar {
+            self.draw_hover_label(rectangle, origin, scene);
+        }
+    }
+
+    /// Shows the value of the bar beyond its end, or that it is missing. The value is taken from
+    /// the data, the bar only knows its f32 drawing value.
+    fn draw_hover_label(&self, bar: &BarHitInfo, origin: Point, scene: &mut Scene) {
+        let text = match self.get_shown_value(bar.dataset_id, bar.slot) {
+            Some(value) => value.to_string(),
+            None => String::from(NO_DATA_TEXT),
+        };
+        let text_width = util::placeholder_get_text_width(&text);
+        let text_height = util::placeholder_get_text_height(&text);
+        let config = &self.coordinate_system.coordinate_system_config;
+        let bar = bar.bar;
+        // bars without length lie on the axis, their label goes to the side of the value axis
+        let (text_position, text_color, text_size) = if self.data_axis
+            == BarChartDataAxis::XPositive
+            || self.data_axis == BarChartDataAxis::XNegative
+        {
+            let center_y = bar.y + bar.height / 2.0;
+            let above = center_y < origin.y
+                || (center_y == origin.y
+                    && self.coordinate_system.y_axis_orientation != YAxisOrientation::Negative);
+            let y = if above {
+                bar.y - text_height
+            } else {
+                bar.y + bar.height
+            };
+            (
+                Point::new(bar.x + (bar.width - text_width) / 2.0, y),
+                config.y_text_color,
+                config.y_text_size,
+            )
+        } else {
+            let center_x = bar.x + bar.width / 2.0;
+            let right = center_x > origin.x
+                || (center_x == origin.x
+                    && self.coordinate_system.x_axis_orientation != XAxisOrientation::Negative);
+            let x = if right {
+                bar.x + bar.width
+            } else {
+                bar.x - text_width
+            };
+            (
+                Point::new(x, bar.y + (bar.height - text_height) / 2.0),
+                config.x_text_color,
+                config.x_text_size,
+            )
+        };
+        // kept inside the canvas, e.g. for the biggest bar reaching the end of the axis
+        let text_position = Point::new(
+            text_position.x.min(scene.width() - text_width).max(0.0),
+            text_position.y.min(scene.height() - text_height).max(0.0),
+        );
+        scene.fill_rectangle(
+            text_position,
+            Size::new(text_width, text_height),
+            self.style
+                .background_color
+                .unwrap_or(HOVER_LABEL_BACKGROUND_COLOR),
+        );
+        scene.stroke_rectangle(
+            text_position,
+            Size::new(text_width, text_height),
+            HOVER_LABEL_STROKE_COLOR,
+            HOVER_LABEL_STROKE_WIDTH,
+        );
+        scene.fill_text(text, text_position, text_color, text_size);
+    }
+
+    fn draw_selected_bar(&self, bar: Rectangle, origin: Point, fill: Fill, scene: &mut Scene) {
+        let fill = self
+            .style
+            .selection_fill_color
+            .map(Fill::Solid)
+            .unwrap_or(fill);
+        let radii = self.get_bar_corner_radii(&bar, origin);
+        scene.fill_rounded_rectangle(Point::new(bar.x, bar.y), bar.size(), radii, &fill);
+        scene.stroke_rounded_rectangle(
+            Point::new(bar.x, bar.y),
+            bar.size(),
+            radii,
+            self.style
+                .selection_stroke_color
+                .unwrap_or(SELECTION_STROKE_COLOR),
+            self.style
+                .selection_stroke_width
+                .unwrap_or(SELECTION_STROKE_WIDTH),
+        );
+    }
+
+    fn draw_selection_rectangle(&self, scene: &mut Scene) {
+        if let (Some(drag_start), Some(drag_current)) = (self.drag_start, self.drag_current) {
+            let selection_rectangle = rectangle_from_points(drag_start, drag_current);
+            let color = self
+                .style
+                .selection_rectangle_color
+                .unwrap_or(SELECTION_RECTANGLE_COLOR);
+            scene.fill_rectangle(
+                Point::new(selection_rectangle.x, selection_rectangle.y),
+                selection_rectangle.size(),
+                color,
+            );
+            scene.stroke_rectangle(
+                Point::new(selection_rectangle.x, selection_rectangle.y),
+                selection_rectangle.size(),
+                Color { a: 1.0, ..color },
+                1.0,
+            );
+        }
+    }
+
+    /// returns the value corresponding to the position on the value axis
+    fn calculate_value_at_position(&self, axis_and_origin: &AxisAndOrigin, position: Point) -> f64 {
+        let (
+            x_axis_positive_length,
+            x_axis_negative_length,
+            y_axis_positive_length,
+            y_axis_negative_length,
+            origin,
+        ) = axis_and_origin.get_axis_and_origin();
+        let (offset, axis_length) = if self.data_axis == BarChartDataAxis::XPositive
+            || self.data_axis == BarChartDataAxis::XNegative
+        {
+            let offset = origin.y - position.y;
+            if offset >= 0.0 {
+                (offset, y_axis_positive_length)
+            } else {
+                (offset, y_axis_negative_length)
+            }
+        } else {
+            let offset = position.x - origin.x;
+            if offset >= 0.0 {
+                (offset, x_axis_positive_length)
+            } else {
+                (offset, x_axis_negative_length)
+            }
+        };
+        if axis_length <= 0.0 {
+            0.0
+        } else {
+            (offset / axis_length) as f64 * self.biggest_data_entry_abs
+        }
+    }
+
+    fn draw_crosshair(&self, axis_and_origin: &AxisAndOrigin, position: Point, scene: &mut Scene) {
+        let origin = axis_and_origin.origin;
+        let plot_area = axis_and_origin.get_plot_area();
+        let color = self.style.crosshair_color.unwrap_or(CROSSHAIR_COLOR);
+        let stroke_width = self
+            .style
+            .crosshair_stroke_width
+            .unwrap_or(CROSSHAIR_STROKE_WIDTH);
+
+        if let Some((_, label_cell)) = self
+            .coordinate_system
+            .get_cluster_label_cell_at(axis_and_origin, position)
+        {
+            scene.fill_rectangle(
+                Point::new(label_cell.x, label_cell.y),
+                label_cell.size(),
+                self.style
+                    .crosshair_highlight_color
+                    .unwrap_or(CROSSHAIR_HIGHLIGHT_COLOR),
+            );
+        }
+
+        scene.stroke_line(
+            Point::new(position.x, plot_area.y),
+            Point::new(position.x, plot_area.y + plot_area.height),
+            color,
+            stroke_width,
+        );
+        scene.stroke_line(
+            Point::new(plot_area.x, position.y),
+            Point::new(plot_area.x + plot_area.width, position.y),
+            color,
+            stroke_width,
+        );
+
+        let value = self.calculate_value_at_position(axis_and_origin, position);
+        let text = T::format_axis_value(value);
+        let text_width = util::placeholder_get_text_width(&text);
+        let text_height = util::placeholder_get_text_height(&text);
+        let config = &self.coordinate_system.coordinate_system_config;
+        let (text_position, text_color, text_size) = if self.data_axis
+            == BarChartDataAxis::XPositive
+            || self.data_axis == BarChartDataAxis::XNegative
+        {
+            let text_position = match self.coordinate_system.x_axis_orientation {
+                XAxisOrientation::Negative => Point::new(origin.x, position.y - text_height / 2.0),
+                _ => Point::new(origin.x - text_width, position.y - text_height / 2.0),
+            };
+            (text_position, config.y_text_color, config.y_text_size)
+        } else {
+            let text_position = match self.coordinate_system.y_axis_orientation {
+                YAxisOrientation::Negative => {
+                    Point::new(position.x - text_width / 2.0, origin.y - text_height)
+                }
+                _ => Point::new(position.x - text_width / 2.0, origin.y),
+            };
+            (text_position, config.x_text_color, config.x_text_size)
+        };
+        scene.fill_rectangle(
+            text_position,
+            Size::new(text_width, text_height),
+            self.style
+                .background_color
+                .unwrap_or(CROSSHAIR_LABEL_BACKGROUND_COLOR),
+        );
+        scene.stroke_rectangle(
+            text_position,
+            Size::new(text_width, text_height),
+            color,
+            stroke_width,
+        );
+        scene.fill_text(text, text_position, text_color, text_size);
+    }
+
+    /// applies the theme colors to the style of this chart
+    pub fn set_theme(&mut self, theme: &Theme) {
+        theme.apply(&mut self.style);
+        self.coordinate_system.coordinate_system_config = update_config(
+            &self.data_axis,
+            &self.coordinate_system.x_axis_orientation,
+            &self.coordinate_system.y_axis_orientation,
+            self.get_shown_data(),
+            self.get_shown_cluster_names(),
+            &self.style,
+            self.biggest_data_entry_abs,
+        );
+        self.clear_caches();
+        self.invalidate_layout();
     }
 
     pub fn set_headline(&mut self, headline: String) {
+        self.style.headline = Some(headline.clone());
         self.coordinate_system.coordinate_system_config.headline = Some(headline);
+        self.clear_caches();
+        self.invalidate_layout();
+    }
+}
+
+/// returns (dataset_id, cluster_index) of a bar that can be selected
+fn get_selectable_bar(bar: &BarHitInfo) -> Option<(u32, usize)> {
+    match (bar.cluster_index, bar.value) {
+        (Some(cluster_index), Some(_)) => Some((bar.dataset_id, cluster_index)),
+        _ => None,
     }
 }
 
-fn update_config(
+fn get_sorted_clusters<T: ChartValue>(
+    data: &BarChartData<T>,
+    cluster_names: &[String],
+    style: &BarChartStyleInfoIced,
+) -> Option<SortedClusters<T>> {
+    sort_clusters(
+        data,
+        cluster_names,
+        style.cluster_sorting,
+        style.max_shown_clusters,
+        style
+            .other_cluster_name
+            .as_deref()
+            .unwrap_or(OTHER_CLUSTER_NAME),
+    )
+}
+
+fn update_config<T: ChartValue>(
     data_axis: &BarChartDataAxis,
     x_axis_orientation: &XAxisOrientation,
     y_axis_orientation: &YAxisOrientation,
-    data: &BarChartData,
+    data: &BarChartData<T>,
     cluster_names: &[String],
     style: &BarChartStyleInfoIced,
+    biggest_entry_abs: f64,
 ) -> CoordinateSystemConfig {
     /*
     x_text,
@@ -629,17 +1926,6 @@ fn update_config(
     headline_size: TEXT_SIZE,
     markings_text_size: TEXT_SIZE,*/
 
-    let biggest_entry_abs = if let Some(max_value) = style.max_value {
-        let biggest_entry_abs = data.get_biggest_data_entry_abs();
-        if max_value > biggest_entry_abs {
-            max_value
-        } else {
-            biggest_entry_abs
-        }
-    } else {
-        data.get_biggest_data_entry_abs()
-    };
-
     let mut coordinate_system_config = CoordinateSystemConfig::new();
     coordinate_system_config.x_max_value = 0.0;
     coordinate_system_config.x_min_value = 0.0;
@@ -810,7 +2096,25 @@ fn update_config(
         }
     }
 
+    let (cluster_text_color, markings_text_color) = match data_axis {
+        BarChartDataAxis::XPositive | BarChartDataAxis::XNegative => (
+            &mut coordinate_system_config.x_text_color,
+            &mut coordinate_system_config.y_text_color,
+        ),
+        BarChartDataAxis::YPositive | BarChartDataAxis::YNegative => (
+            &mut coordinate_system_config.y_text_color,
+            &mut coordinate_system_config.x_text_color,
+        ),
+    };
+    if let Some(cluster_color) = style.cluster_color {
+        *cluster_text_color = cluster_color;
+    }
+    if let Some(markings_color) = style.markings_color {
+        *markings_text_color = markings_color;
+    }
+
     coordinate_system_config.x_marking_amount = style.x_marking_amount;
+    coordinate_system_config.value_formatter = T::format_axis_value;
     coordinate_system_config.y_marking_amount = style.y_marking_amount;
 
     if let Some(axis_stroke_width) = style.axis_stroke_width {
@@ -835,6 +2139,10 @@ fn update_config(
         coordinate_system_config.headline_size = headline_size;
     }
     coordinate_system_config.legend_alignment = style.legend_alignment;
+    coordinate_system_config.legend_entries = create_legend_entries(data, style);
+    if let Some(legend_text_color) = style.legend_text_color {
+        coordinate_system_config.legend_text_color = legend_text_color;
+    }
 
     if let Some(text_padding) = style.text_padding {
         coordinate_system_config.text_padding = text_padding;
@@ -842,15 +2150,73 @@ fn update_config(
 
     coordinate_system_config
 }
-fn calculate_biggest_data_value(style: &BarChartStyleInfoIced, data: &BarChartData) -> f32 {
-    if let Some(max_value) = style.max_value {
-        let biggest_entry_abs = data.get_biggest_data_entry_abs();
-        if max_value.abs() > biggest_entry_abs {
-            max_value.abs()
-        } else {
-            biggest_entry_abs
+fn get_dataset_color(style: &BarChartStyleInfoIced, dataset_id: u32) -> Color {
+    match style.color_map.get(&dataset_id) {
+        Some(color) => *color,
+        None => palette::get_cycled_color(&style.standard_color_map, dataset_id as usize),
+    }
+}
+
+fn get_dataset_fill(style: &BarChartStyleInfoIced, dataset_id: u32) -> Fill {
+    match style.fill_map.get(&dataset_id) {
+        Some(fill) => fill.clone(),
+        None => Fill::Solid(get_dataset_color(style, dataset_id)),
+    }
+}
+
+/// One entry per dataset followed by the entries of the color rules.
+/// The datasets are left out if a color scale decides the color of every bar.
+fn create_legend_entries<T: ChartValue>(
+    data: &BarChartData<T>,
+    style: &BarChartStyleInfoIced,
+) -> Vec<LegendEntry> {
+    let data_range = data.get_data_range();
+    let mut legend_entries = vec![];
+    let colored_by_scale = style
+        .bar_color_rules
+        .iter()
+        .any(|rule| matches!(rule, BarColorRule::Scale { .. }));
+    if !colored_by_scale {
+        for dataset in &data.datasets {
+            legend_entries.push(LegendEntry::with_fill(
+                dataset.name.clone(),
+                get_dataset_fill(style, dataset.id),
+            ));
         }
+    }
+    for rule in &style.bar_color_rules {
+        legend_entries.extend(rule.get_legend_entries(data_range));
+    }
+    legend_entries
+}
+
+/// Returns the biggest value of the value axis. Its markings are evenly spaced from 0 to this
+/// value, so it is rounded up until the steps between them suit `T`, see `round_axis_step`.
+/// It is never 0, even if all values are 0 or missing.
+fn calculate_biggest_data_value<T: ChartValue>(
+    style: &BarChartStyleInfoIced,
+    data_axis: BarChartDataAxis,
+    data: &BarChartData<T>,
+) -> f64 {
+    let biggest_entry_abs = data.get_biggest_data_entry_abs();
+    let biggest_value = match style.max_value {
+        Some(max_value) => (max_value as f64).abs().max(biggest_entry_abs),
+        None => biggest_entry_abs,
+    };
+    let biggest_value = if biggest_value > 0.0 && biggest_value.is_finite() {
+        biggest_value
     } else {
-        data.get_biggest_data_entry_abs()
+        EMPTY_VALUE_AXIS_END
+    };
+    let marking_amount = match data_axis {
+        BarChartDataAxis::XPositive | BarChartDataAxis::XNegative => style.y_marking_amount,
+        BarChartDataAxis::YPositive | BarChartDataAxis::YNegative => style.x_marking_amount,
+    };
+    match marking_amount {
+        Some(marking_amount) if marking_amount > 1 => {
+            let steps = (marking_amount - 1) as f64;
+            T::round_axis_step(biggest_value / steps) * steps
+        }
+        _ => biggest_value,
     }
 }
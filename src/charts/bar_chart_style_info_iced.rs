@@ -1,101 +1,118 @@
+use crate::charts::animation::Easing;
+use crate::charts::bar_color_rule::BarColorRule;
+use crate::charts::cluster_sorting::ClusterSorting;
 use crate::charts::coordinate_system::CoordinateSystemLegendAlignment;
+use crate::charts::fill::Fill;
+use crate::charts::palette::QualitativePalette;
+use crate::charts::serde_color;
 use iced::Color;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-const STANDARD_COLOR_MAP: [Color; 11] = [
-    Color::from_rgb(
-        0x58 as f32 / 255.0,
-        0x99 as f32 / 255.0,
-        0xDA as f32 / 255.0,
-    ),
-    Color::from_rgb(
-        0xE8 as f32 / 255.0,
-        0x74 as f32 / 255.0,
-        0x3B as f32 / 255.0,
-    ),
-    Color::from_rgb(
-        0x19 as f32 / 255.0,
-        0xA9 as f32 / 255.0,
-        0x79 as f32 / 255.0,
-    ),
-    Color::from_rgb(
-        0xED as f32 / 255.0,
-        0x4A as f32 / 255.0,
-        0x7B as f32 / 255.0,
-    ),
-    Color::from_rgb(
-        0x94 as f32 / 255.0,
-        0x5E as f32 / 255.0,
-        0xCF as f32 / 255.0,
-    ),
-    Color::from_rgb(
-        0x13 as f32 / 255.0,
-        0xA4 as f32 / 255.0,
-        0xB4 as f32 / 255.0,
-    ),
-    Color::from_rgb(
-        0x52 as f32 / 255.0,
-        0x5D as f32 / 255.0,
-        0xF4 as f32 / 255.0,
-    ),
-    Color::from_rgb(
-        0xBF as f32 / 255.0,
-        0x39 as f32 / 255.0,
-        0x9E as f32 / 255.0,
-    ),
-    Color::from_rgb(
-        0x6C as f32 / 255.0,
-        0x88 as f32 / 255.0,
-        0x93 as f32 / 255.0,
-    ),
-    Color::from_rgb(
-        0xEE as f32 / 255.0,
-        0x68 as f32 / 255.0,
-        0x68 as f32 / 255.0,
-    ),
-    Color::from_rgb(
-        0x2F as f32 / 255.0,
-        0x64 as f32 / 255.0,
-        0x97 as f32 / 255.0,
-    ),
-];
-
-#[derive(Debug)]
+/// Missing fields take their default value when deserializing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct BarChartStyleInfoIced {
+    #[serde(with = "serde_color::color_map")]
     pub color_map: HashMap<u32, Color>,
-    pub standard_color_map: [Color; 11],
-    pub axis_stroke_width: Option<f32>,        //
-    pub axis_stroke_color: Option<Color>,      //
-    pub separator_stroke_width: Option<f32>,   //
+    /// colors for datasets without an entry in `color_map`, see `palette` for predefined ones.
+    /// If there are more datasets than colors, lighter and darker shades are used.
+    #[serde(with = "serde_color::color_vec")]
+    pub standard_color_map: Vec<Color>,
+    /// gradients or hatch patterns per dataset id, taking precedence over `color_map`
+    pub fill_map: HashMap<u32, Fill>,
+    /// colors bars by their value, see `BarColorRule`. Checked in order before the dataset colors.
+    pub bar_color_rules: Vec<BarColorRule>,
+    pub axis_stroke_width: Option<f32>, //
+    #[serde(with = "serde_color::option_color")]
+    pub axis_stroke_color: Option<Color>, //
+    pub separator_stroke_width: Option<f32>, //
+    #[serde(with = "serde_color::option_color")]
     pub separator_stroke_color: Option<Color>, //
     pub maximum_bar_width: f32,
+    /// rounds the corners at the end of a bar facing away from the axis
+    pub bar_corner_radius: Option<f32>,
+    /// bars get an outline if set
+    #[serde(with = "serde_color::option_color")]
+    pub bar_outline_color: Option<Color>,
+    pub bar_outline_width: Option<f32>,
+    pub show_bar_shadow: bool,
+    #[serde(with = "serde_color::option_color")]
+    pub bar_shadow_color: Option<Color>,
+    /// (x, y) offset of the shadow from its bar
+    pub bar_shadow_offset: Option<(f32, f32)>,
     pub min_cluster_spacing: f32,
     pub bar_spacing: f32,
     pub x_marking_amount: Option<usize>, //
     pub y_marking_amount: Option<usize>, //
     pub max_value: Option<f32>,          //
-    pub markings_color: Option<Color>,   //
-    pub cluster_color: Option<Color>,    //
+    #[serde(with = "serde_color::option_color")]
+    pub markings_color: Option<Color>, //
+    #[serde(with = "serde_color::option_color")]
+    pub cluster_color: Option<Color>, //
     pub headline: Option<String>,        //
-    pub headline_color: Option<Color>,   //
+    #[serde(with = "serde_color::option_color")]
+    pub headline_color: Option<Color>, //
     pub legend_alignment: Option<CoordinateSystemLegendAlignment>, //
+    #[serde(with = "serde_color::option_color")]
+    pub legend_text_color: Option<Color>,
     pub text_padding: Option<f32>,       //
     pub cluster_text_size: Option<f32>,  //
     pub headline_size: Option<f32>,      //
     pub markings_text_size: Option<f32>, //
+    #[serde(with = "serde_color::option_color")]
+    pub selection_fill_color: Option<Color>,
+    #[serde(with = "serde_color::option_color")]
+    pub selection_stroke_color: Option<Color>,
+    pub selection_stroke_width: Option<f32>,
+    #[serde(with = "serde_color::option_color")]
+    pub selection_rectangle_color: Option<Color>,
+    #[serde(with = "serde_color::option_color")]
+    pub hover_color: Option<Color>,
+    /// draws a cross where a missing value would be, otherwise missing values are just gaps
+    pub show_missing_value_marker: bool,
+    #[serde(with = "serde_color::option_color")]
+    pub missing_value_marker_color: Option<Color>,
+    pub show_crosshair: bool,
+    #[serde(with = "serde_color::option_color")]
+    pub crosshair_color: Option<Color>,
+    pub crosshair_stroke_width: Option<f32>,
+    #[serde(with = "serde_color::option_color")]
+    pub crosshair_highlight_color: Option<Color>,
+    #[serde(with = "serde_color::option_color")]
+    pub background_color: Option<Color>,
+    /// data changes are animated over this many milliseconds, not at all if `None`
+    pub animation_duration_ms: Option<u64>,
+    pub animation_easing: Option<Easing>,
+    /// Accepts empty data and datasets not matching the cluster names, and turns non-finite
+    /// values into missing values instead of returning errors. Duplicate dataset ids are never accepted.
+    pub lenient_validation: bool,
+    /// the clusters are shown sorted, the data itself keeps its order
+    pub cluster_sorting: Option<ClusterSorting>,
+    /// only this many clusters are shown, the rest is summed up into one other cluster
+    pub max_shown_clusters: Option<usize>,
+    /// name of the cluster summing up the ones beyond `max_shown_clusters`, "Other" by default
+    pub other_cluster_name: Option<String>,
 }
 
 impl BarChartStyleInfoIced {
     pub fn new() -> Self {
-        //TODO: Create own own color palette. Colors taken from Qualitative Palette: https://experience.sap.com/fiori-design-web/values-and-names/
         BarChartStyleInfoIced {
             color_map: HashMap::new(),
-            standard_color_map: STANDARD_COLOR_MAP,
+            standard_color_map: QualitativePalette::default().colors(),
+            fill_map: HashMap::new(),
+            bar_color_rules: vec![],
             axis_stroke_width: None,
             axis_stroke_color: None,
             separator_stroke_width: None,
             separator_stroke_color: None,
             maximum_bar_width: 50.0,
+            bar_corner_radius: None,
+            bar_outline_color: None,
+            bar_outline_width: None,
+            show_bar_shadow: false,
+            bar_shadow_color: None,
+            bar_shadow_offset: None,
             min_cluster_spacing: 5.0,
             bar_spacing: 2.0,
             x_marking_amount: Some(5),
@@ -106,10 +123,35 @@ impl BarChartStyleInfoIced {
             headline: None,
             headline_color: None,
             legend_alignment: None,
+            legend_text_color: None,
             text_padding: None,
             cluster_text_size: None,
             headline_size: None,
             markings_text_size: None,
+            selection_fill_color: None,
+            selection_stroke_color: None,
+            selection_stroke_width: None,
+            selection_rectangle_color: None,
+            hover_color: None,
+            show_missing_value_marker: false,
+            missing_value_marker_color: None,
+            show_crosshair: false,
+            crosshair_color: None,
+            crosshair_stroke_width: None,
+            crosshair_highlight_color: None,
+            background_color: None,
+            animation_duration_ms: None,
+            animation_easing: None,
+            lenient_validation: false,
+            cluster_sorting: None,
+            max_shown_clusters: None,
+            other_cluster_name: None,
         }
     }
 }
+
+impl Default for BarChartStyleInfoIced {
+    fn default() -> Self {
+        BarChartStyleInfoIced::new()
+    }
+}
@@ -0,0 +1,132 @@
+use crate::charts::coordinate_system::LegendEntry;
+use crate::charts::palette::ColorRamp;
+use crate::charts::serde_color;
+use iced::Color;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+
+/// Computes the color of a bar from (dataset_id, cluster_index, value),
+/// `None` leaves the bar to the next rule. The cluster index is the index in the data,
+/// the cluster summing up the clusters that aren't shown gets the index past the last cluster.
+#[derive(Clone)]
+pub struct BarColorFunction(pub Arc<dyn Fn(u32, usize, f32) -> Option<Color> + Send + Sync>);
+
+impl BarColorFunction {
+    pub fn new<F>(function: F) -> Self
+    where
+        F: Fn(u32, usize, f32) -> Option<Color> + Send + Sync + 'static,
+    {
+        BarColorFunction(Arc::new(function))
+    }
+}
+
+impl fmt::Debug for BarColorFunction {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("BarColorFunction")
+    }
+}
+
+/// Colors a bar by its value instead of by its dataset.
+/// The rules of a style are checked in order, the first matching one wins,
+/// bars matched by no rule keep their dataset color.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum BarColorRule {
+    /// values below 0
+    Negative {
+        #[serde(with = "serde_color::color")]
+        color: Color,
+    },
+    /// values bigger than `threshold`
+    Above {
+        threshold: f32,
+        #[serde(with = "serde_color::color")]
+        color: Color,
+    },
+    /// values smaller than `threshold`
+    Below {
+        threshold: f32,
+        #[serde(with = "serde_color::color")]
+        color: Color,
+    },
+    /// Every value gets a color of the ramp, `min` and `max` default to the smallest and biggest value of the data.
+    Scale {
+        ramp: ColorRamp,
+        min: Option<f32>,
+        max: Option<f32>,
+    },
+    /// Only shows up in the legend if `legend` is set. Can't be serialized, charts
+    /// exported as a specification lose these rules.
+    #[serde(skip)]
+    Custom {
+        function: BarColorFunction,
+        legend: Option<(String, Color)>,
+    },
+}
+
+impl BarColorRule {
+    /// returns the color of the bar, or `None` if the rule doesn't apply to it.
+    /// `data_range` is (smallest value, biggest value) of the chart.
+    pub fn get_color(
+        &self,
+        dataset_id: u32,
+        cluster_index: usize,
+        value: f32,
+        data_range: (f32, f32),
+    ) -> Option<Color> {
+        match self {
+            BarColorRule::Negative { color } if value < 0.0 => Some(*color),
+            BarColorRule::Above { threshold, color } if value > *threshold => Some(*color),
+            BarColorRule::Below { threshold, color } if value < *threshold => Some(*color),
+            BarColorRule::Scale { ramp, min, max } => Some(ramp.color_for_value(
+                value,
+                min.unwrap_or(data_range.0),
+                max.unwrap_or(data_range.1),
+            )),
+            BarColorRule::Custom { function, .. } => (function.0)(dataset_id, cluster_index, value),
+            _ => None,
+        }
+    }
+
+    pub fn is_custom(&self) -> bool {
+        matches!(self, BarColorRule::Custom { .. })
+    }
+
+    /// returns the entries describing this rule in the legend
+    pub fn get_legend_entries(&self, data_range: (f32, f32)) -> Vec<LegendEntry> {
+        match self {
+            BarColorRule::Negative { color } => vec![LegendEntry::new(String::from("< 0"), *color)],
+            BarColorRule::Above { threshold, color } => {
+                vec![LegendEntry::new(format!("> {}", threshold), *color)]
+            }
+            BarColorRule::Below { threshold, color } => {
+                vec![LegendEntry::new(format!("< {}", threshold), *color)]
+            }
+            BarColorRule::Scale { ramp, min, max } => {
+                let min = min.unwrap_or(data_range.0);
+                let max = max.unwrap_or(data_range.1);
+                vec![
+                    LegendEntry::new(min.to_string(), ramp.color_at(0.0)),
+                    LegendEntry::new(max.to_string(), ramp.color_at(1.0)),
+                ]
+            }
+            BarColorRule::Custom { legend, .. } => legend
+                .iter()
+                .map(|(label, color)| LegendEntry::new(label.clone(), *color))
+                .collect(),
+        }
+    }
+}
+
+/// returns the color of the first matching rule
+pub fn get_rule_color(
+    rules: &[BarColorRule],
+    dataset_id: u32,
+    cluster_index: usize,
+    value: f32,
+    data_range: (f32, f32),
+) -> Option<Color> {
+    rules
+        .iter()
+        .find_map(|rule| rule.get_color(dataset_id, cluster_index, value, data_range))
+}
@@ -0,0 +1,180 @@
+use crate::charts::coordinate_system::DrawingAxis;
+use crate::charts::util::rectangles_overlap;
+use iced::{Point, Rectangle};
+
+#[derive(Debug, Clone, Copy)]
+pub struct BarHitInfo {
+    /// The area actually filled by the bar.
+    pub bar: Rectangle,
+    /// The bar extended over the whole value axis, used for hovering and clicking.
+    pub hover_area: Rectangle,
+    pub dataset_id: u32,
+    /// position of the cluster in the layout, differs from the cluster index if the clusters are sorted
+    pub slot: usize,
+    /// index of the cluster in the data, `None` for the cluster summing up the clusters that
+    /// aren't shown, see `ClusterSorting`
+    pub cluster_index: Option<usize>,
+    /// `None` for missing values, their bar has no area
+    pub value: Option<f32>,
+}
+
+/// Bars bucketed by the slot of their cluster. Slots are evenly spaced along the cluster axis,
+/// so the bucket for a position is found without searching.
+#[derive(Debug, Clone)]
+pub struct BarHitIndex {
+    bars: Vec<BarHitInfo>,
+    cluster_buckets: Vec<Vec<usize>>,
+    cluster_axis: DrawingAxis,
+    cluster_axis_start: f32,
+    /// signed distance between two slots, negative if clusters are laid out towards smaller coordinates
+    cluster_step: f32,
+}
+
+impl BarHitIndex {
+    pub fn new() -> Self {
+        BarHitIndex {
+            bars: vec![],
+            cluster_buckets: vec![],
+            cluster_axis: DrawingAxis::X,
+            cluster_axis_start: 0.0,
+            cluster_step: 0.0,
+        }
+    }
+
+    pub fn from_bars(
+        bars: Vec<BarHitInfo>,
+        slot_amount: usize,
+        cluster_axis: DrawingAxis,
+        cluster_axis_start: f32,
+        cluster_step: f32,
+    ) -> Self {
+        let mut cluster_buckets = vec![vec![]; slot_amount];
+        for (bar_index, bar) in bars.iter().enumerate() {
+            if let Some(bucket) = cluster_buckets.get_mut(bar.slot) {
+                bucket.push(bar_index);
+            }
+        }
+        BarHitIndex {
+            bars,
+            cluster_buckets,
+            cluster_axis,
+            cluster_axis_start,
+            cluster_step,
+        }
+    }
+
+    /// Drops the bars of the first `amount` slots and moves the others forward by as many slots,
+    /// their cluster indices along with them. `appended` holds the bars of the slots freed at the
+    /// end. Only for clusters that aren't sorted, where slot and cluster index are the same.
+    pub fn shift_slots(&mut self, amount: usize, appended: BarHitIndex) {
+        let offset = -(amount as f32) * self.cluster_step;
+        let mut bars: Vec<BarHitInfo> = self
+            .bars
+            .drain(..)
+            .filter(|bar| bar.slot >= amount)
+            .collect();
+        for bar in &mut bars {
+            bar.slot -= amount;
+            bar.cluster_index = bar
+                .cluster_index
+                .map(|cluster_index| cluster_index.saturating_sub(amount));
+            match self.cluster_axis {
+                DrawingAxis::X => {
+                    bar.bar.x += offset;
+                    bar.hover_area.x += offset;
+                }
+                DrawingAxis::Y => {
+                    bar.bar.y += offset;
+                    bar.hover_area.y += offset;
+                }
+            }
+        }
+        bars.extend(appended.bars);
+        *self = BarHitIndex::from_bars(
+            bars,
+            self.cluster_buckets.len(),
+            self.cluster_axis,
+            self.cluster_axis_start,
+            self.cluster_step,
+        );
+    }
+
+    pub fn get_slot_amount(&self) -> usize {
+        self.cluster_buckets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bars.is_empty()
+    }
+
+    /// returns all bars in drawing order
+    pub fn get_bars(&self) -> &[BarHitInfo] {
+        &self.bars
+    }
+
+    pub fn get_bars_mut(&mut self) -> &mut [BarHitInfo] {
+        &mut self.bars
+    }
+
+    pub fn get_bar(&self, dataset_id: u32, slot: usize) -> Option<&BarHitInfo> {
+        self.cluster_buckets
+            .get(slot)?
+            .iter()
+            .map(|bar_index| &self.bars[*bar_index])
+            .find(|bar| bar.dataset_id == dataset_id)
+    }
+
+    pub fn get_slot_at(&self, position: Point) -> Option<usize> {
+        let slot = self.calculate_unbounded_slot(position)?;
+        if slot < 0.0 || slot >= self.cluster_buckets.len() as f32 {
+            None
+        } else {
+            Some(slot as usize)
+        }
+    }
+
+    pub fn get_bar_at(&self, position: Point) -> Option<&BarHitInfo> {
+        let slot = self.get_slot_at(position)?;
+        self.cluster_buckets[slot]
+            .iter()
+            .map(|bar_index| &self.bars[*bar_index])
+            .find(|bar| bar.hover_area.contains(position))
+    }
+
+    /// returns all bars whose filled area overlaps the rectangle
+    pub fn get_bars_in_rectangle(&self, rectangle: &Rectangle) -> Vec<&BarHitInfo> {
+        if self.cluster_buckets.is_empty() {
+            return vec![];
+        }
+        let first = self.calculate_unbounded_slot(Point::new(rectangle.x, rectangle.y));
+        let second = self.calculate_unbounded_slot(Point::new(
+            rectangle.x + rectangle.width,
+            rectangle.y + rectangle.height,
+        ));
+        let (first, second) = match (first, second) {
+            (Some(first), Some(second)) => (first, second),
+            _ => return vec![],
+        };
+        let last_bucket = self.cluster_buckets.len() as f32 - 1.0;
+        let start = first.min(second).max(0.0).min(last_bucket) as usize;
+        let end = first.max(second).max(0.0).min(last_bucket) as usize;
+
+        self.cluster_buckets[start..=end]
+            .iter()
+            .flatten()
+            .map(|bar_index| &self.bars[*bar_index])
+            .filter(|bar| rectangles_overlap(&bar.bar, rectangle))
+            .collect()
+    }
+
+    fn calculate_unbounded_slot(&self, position: Point) -> Option<f32> {
+        if self.cluster_step == 0.0 || !self.cluster_step.is_finite() {
+            return None;
+        }
+        let coordinate = match self.cluster_axis {
+            DrawingAxis::X => position.x,
+            DrawingAxis::Y => position.y,
+        };
+        Some(((coordinate - self.cluster_axis_start) / self.cluster_step).floor())
+    }
+}
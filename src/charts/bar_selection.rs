@@ -0,0 +1,117 @@
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Default)]
+pub struct BarSelection {
+    selected: HashSet<(u32, usize)>,
+    anchor: Option<(u32, usize)>,
+}
+
+impl BarSelection {
+    pub fn new() -> Self {
+        BarSelection {
+            selected: HashSet::new(),
+            anchor: None,
+        }
+    }
+
+    pub fn contains(&self, dataset_id: u32, cluster_index: usize) -> bool {
+        self.selected.contains(&(dataset_id, cluster_index))
+    }
+
+    pub fn get_selected(&self) -> &HashSet<(u32, usize)> {
+        &self.selected
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.selected.is_empty()
+    }
+
+    /// returns the selection sorted by (dataset_id, cluster_index)
+    pub fn to_sorted_vec(&self) -> Vec<(u32, usize)> {
+        let mut selected: Vec<(u32, usize)> = self.selected.iter().copied().collect();
+        selected.sort_unstable();
+        selected
+    }
+
+    pub fn replace(&mut self, selected: HashSet<(u32, usize)>) {
+        self.selected = selected;
+        self.anchor = None;
+    }
+
+    pub fn clear(&mut self) {
+        self.selected.clear();
+        self.anchor = None;
+    }
+
+    pub fn select_single(&mut self, bar: (u32, usize)) {
+        self.selected.clear();
+        self.selected.insert(bar);
+        self.anchor = Some(bar);
+    }
+
+    pub fn toggle(&mut self, bar: (u32, usize)) {
+        if !self.selected.remove(&bar) {
+            self.selected.insert(bar);
+        }
+        self.anchor = Some(bar);
+    }
+
+    /// Selects every bar between the anchor (the last clicked bar) and `bar`.
+    /// `bars_in_drawing_order` defines which bars lie in between.
+    pub fn select_range(&mut self, bar: (u32, usize), bars_in_drawing_order: &[(u32, usize)]) {
+        let anchor = match self.anchor {
+            Some(anchor) => anchor,
+            None => {
+                self.select_single(bar);
+                return;
+            }
+        };
+        let anchor_position = bars_in_drawing_order.iter().position(|x| *x == anchor);
+        let bar_position = bars_in_drawing_order.iter().position(|x| *x == bar);
+        if let (Some(anchor_position), Some(bar_position)) = (anchor_position, bar_position) {
+            let (start, end) = if anchor_position < bar_position {
+                (anchor_position, bar_position)
+            } else {
+                (bar_position, anchor_position)
+            };
+            for entry in &bars_in_drawing_order[start..=end] {
+                self.selected.insert(*entry);
+            }
+        } else {
+            self.select_single(bar);
+        }
+    }
+
+    /// Selects all given bars. If `additive` is false, the previous selection is discarded.
+    pub fn select_all(&mut self, bars: &[(u32, usize)], additive: bool) {
+        if !additive {
+            self.selected.clear();
+        }
+        for bar in bars {
+            self.selected.insert(*bar);
+        }
+        self.anchor = bars.last().copied();
+    }
+
+    /// Moves every selected bar to the bar returned by `remap`, bars mapped to `None` are deselected.
+    pub fn remap<F>(&mut self, mut remap: F)
+    where
+        F: FnMut((u32, usize)) -> Option<(u32, usize)>,
+    {
+        self.selected = self.selected.iter().filter_map(|bar| remap(*bar)).collect();
+        self.anchor = self.anchor.and_then(remap);
+    }
+
+    /// Removes all bars from the selection for which `keep` returns false.
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&(u32, usize)) -> bool,
+    {
+        self.selected.retain(|bar| keep(bar));
+        if let Some(anchor) = self.anchor {
+            if !keep(&anchor) {
+                self.anchor = None;
+            }
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use crate::charts::axis_enums::{BarChartDataAxis, XAxisOrientation, YAxisOrientation};
+use crate::charts::bar_chart_data::Dataset;
+use crate::charts::bar_chart_style_info_iced::BarChartStyleInfoIced;
+use crate::charts::chart_value::ChartValue;
+use crate::charts::error::{ChartsLibError, ErrorKind};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ChartType {
+    Bar,
+}
+
+/// Complete description of a chart, to be shipped as JSON and turned into a live chart.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChartSpec<T = f32> {
+    pub chart_type: ChartType,
+    pub data_axis: BarChartDataAxis,
+    pub x_axis_orientation: XAxisOrientation,
+    pub y_axis_orientation: YAxisOrientation,
+    pub datasets: Vec<Dataset<T>>,
+    pub cluster_names: Vec<String>,
+    #[serde(default)]
+    pub style: BarChartStyleInfoIced,
+}
+
+impl<T: ChartValue> ChartSpec<T> {
+    pub fn from_json(json: &str) -> Result<Self, ChartsLibError> {
+        serde_json::from_str(json).map_err(|error| {
+            ChartsLibError::new(
+                ErrorKind::InvalidChartSpec,
+                format!("Could not read the chart specification: {}", error),
+            )
+        })
+    }
+
+    pub fn to_json(&self) -> Result<String, ChartsLibError> {
+        serde_json::to_string_pretty(self).map_err(|error| {
+            ChartsLibError::new(
+                ErrorKind::InvalidChartSpec,
+                format!("Could not write the chart specification: {}", error),
+            )
+        })
+    }
+}
@@ -0,0 +1,117 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt;
+use std::ops::Add;
+use std::str::FromStr;
+
+/// A number a dataset can hold. Values keep their type in the data and are only converted
+/// to f64 when the chart is laid out. Implement it for decimal types, e.g. money values
+/// stored as cents, to chart them without rounding errors. Clusters are summed up with
+/// `saturating_add`, see `ClusterSorting`.
+pub trait ChartValue:
+    Copy
+    + PartialOrd
+    + Add<Output = Self>
+    + fmt::Debug
+    + fmt::Display
+    + FromStr
+    + Serialize
+    + DeserializeOwned
+    + Send
+    + Sync
+    + 'static
+{
+    fn to_f64(self) -> f64;
+
+    /// converts a value computed in f64 back, e.g. a mean, integer types round it
+    fn from_f64(value: f64) -> Self;
+
+    /// Adds two values without overflowing. By default `+`, types that can overflow have to
+    /// stop at their bounds instead.
+    fn saturating_add(self, other: Self) -> Self {
+        self + other
+    }
+
+    /// NaN and infinite values are rejected by the validation
+    fn is_finite(self) -> bool {
+        true
+    }
+
+    /// Formats a value of the value axis. The markings lie between the values of the data,
+    /// so they are formatted from f64, by default rounded to two decimals.
+    fn format_axis_value(value: f64) -> String {
+        ((value * 100.0).round() / 100.0).to_string()
+    }
+
+    /// Rounds the distance between two markings of the value axis, by default not at all.
+    fn round_axis_step(step: f64) -> f64 {
+        step
+    }
+}
+
+impl ChartValue for f32 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+
+    fn is_finite(self) -> bool {
+        f32::is_finite(self)
+    }
+
+    /// rounded to the precision of an f32, so 0.1 isn't shown as 0.10000000149011612
+    fn format_axis_value(value: f64) -> String {
+        (((value * 100.0).round() / 100.0) as f32).to_string()
+    }
+}
+
+impl ChartValue for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+
+    fn is_finite(self) -> bool {
+        f64::is_finite(self)
+    }
+}
+
+macro_rules! impl_chart_value_for_integer {
+    ($($integer:ty),*) => {
+        $(
+            impl ChartValue for $integer {
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+
+                /// values beyond the bounds of the type become the bound
+                fn from_f64(value: f64) -> Self {
+                    value.round() as $integer
+                }
+
+                fn saturating_add(self, other: Self) -> Self {
+                    <$integer>::saturating_add(self, other)
+                }
+
+                /// Formatted as a whole number, without the rounding to two decimals. Values
+                /// beyond 2^53 are shown as the nearest f64, like all positions on the axis.
+                fn format_axis_value(value: f64) -> String {
+                    (value.round() as i128).to_string()
+                }
+
+                /// whole steps, so every marking is a value of the type
+                fn round_axis_step(step: f64) -> f64 {
+                    step.ceil()
+                }
+            }
+        )*
+    };
+}
+
+impl_chart_value_for_integer!(i32, i64, u32, u64);
@@ -0,0 +1,175 @@
+use crate::charts::bar_chart_data::{BarChartData, Dataset};
+use crate::charts::chart_value::ChartValue;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ClusterSortKey {
+    /// the value of the dataset with this id
+    Dataset(u32),
+    /// the sum of the values of all datasets
+    Total,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+/// Clusters without a value for the key come last in both orders, equal ones keep their order.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ClusterSorting {
+    pub key: ClusterSortKey,
+    pub order: SortOrder,
+}
+
+impl ClusterSorting {
+    pub fn new(key: ClusterSortKey, order: SortOrder) -> Self {
+        ClusterSorting { key, order }
+    }
+
+    fn compare(&self, a: Option<f64>, b: Option<f64>) -> Ordering {
+        match (a, b) {
+            (Some(a), Some(b)) => {
+                let ordering = a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+                match self.order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                }
+            }
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+}
+
+/// The clusters as they are shown, the data of the chart itself stays untouched.
+/// A slot is the position of a shown cluster, the other cluster is in the last slot.
+#[derive(Debug)]
+pub(crate) struct SortedClusters<T> {
+    pub(crate) data: BarChartData<T>,
+    pub(crate) cluster_names: Vec<String>,
+    /// the cluster index of the data shown in each slot, without the other cluster
+    cluster_indices: Vec<usize>,
+    /// the slot of each cluster of the data
+    slots: Vec<usize>,
+}
+
+impl<T> SortedClusters<T> {
+    /// returns the slot showing the cluster, clusters summed up into the other cluster are
+    /// in its slot. `None` if there is no such cluster.
+    pub(crate) fn get_slot(&self, cluster_index: usize) -> Option<usize> {
+        self.slots.get(cluster_index).copied()
+    }
+
+    /// returns the cluster index of the data shown in the slot, `None` for the other cluster
+    pub(crate) fn get_cluster_index(&self, slot: usize) -> Option<usize> {
+        self.cluster_indices.get(slot).copied()
+    }
+}
+
+/// Sorts the clusters and keeps only the first `max_clusters` of them, the values of the others
+/// are summed up per dataset into one cluster named `other_cluster_name`. Without a sorting the
+/// kept clusters are the ones with the biggest totals, in their original order.
+/// Returns `None` if the clusters are shown as they are.
+pub(crate) fn sort_clusters<T: ChartValue>(
+    data: &BarChartData<T>,
+    cluster_names: &[String],
+    sorting: Option<ClusterSorting>,
+    max_clusters: Option<usize>,
+    other_cluster_name: &str,
+) -> Option<SortedClusters<T>> {
+    let cluster_amount = data.get_biggest_amount_of_data_entries_in_one_dataset();
+    let max_clusters = max_clusters.filter(|max_clusters| *max_clusters < cluster_amount);
+    if sorting.is_none() && max_clusters.is_none() {
+        return None;
+    }
+
+    let ranking = sorting
+        .unwrap_or_else(|| ClusterSorting::new(ClusterSortKey::Total, SortOrder::Descending));
+    let sort_values: Vec<Option<f64>> = (0..cluster_amount)
+        .map(|cluster_index| get_sort_value(data, ranking.key, cluster_index))
+        .collect();
+    let mut order: Vec<usize> = (0..cluster_amount).collect();
+    order.sort_by(|a, b| ranking.compare(sort_values[*a], sort_values[*b]));
+
+    let others = match max_clusters {
+        Some(max_clusters) => order.split_off(max_clusters),
+        None => vec![],
+    };
+    if sorting.is_none() {
+        order.sort_unstable();
+    }
+
+    let datasets = data
+        .datasets
+        .iter()
+        .map(|dataset| {
+            let mut values: Vec<Option<T>> = order
+                .iter()
+                .map(|cluster_index| dataset.get_value(*cluster_index))
+                .collect();
+            if !others.is_empty() {
+                values.push(sum_values(dataset, &others));
+            }
+            Dataset::with_missing_values(dataset.id, dataset.name.clone(), values)
+        })
+        .collect();
+    let mut sorted_cluster_names: Vec<String> = order
+        .iter()
+        .map(|cluster_index| {
+            cluster_names
+                .get(*cluster_index)
+                .cloned()
+                .unwrap_or_default()
+        })
+        .collect();
+    if !others.is_empty() {
+        sorted_cluster_names.push(other_cluster_name.to_string());
+    }
+    let mut slots = vec![order.len(); cluster_amount];
+    for (slot, cluster_index) in order.iter().enumerate() {
+        slots[*cluster_index] = slot;
+    }
+    Some(SortedClusters {
+        data: BarChartData::new(datasets),
+        cluster_names: sorted_cluster_names,
+        cluster_indices: order,
+        slots,
+    })
+}
+
+/// `None` if the cluster has no value for the key
+fn get_sort_value<T: ChartValue>(
+    data: &BarChartData<T>,
+    key: ClusterSortKey,
+    cluster_index: usize,
+) -> Option<f64> {
+    match key {
+        ClusterSortKey::Dataset(id) => data
+            .datasets
+            .iter()
+            .find(|dataset| dataset.id == id)?
+            .get_value(cluster_index)
+            .map(ChartValue::to_f64),
+        ClusterSortKey::Total => data
+            .datasets
+            .iter()
+            .filter_map(|dataset| dataset.get_value(cluster_index))
+            .map(ChartValue::to_f64)
+            .fold(None, |sum, value| Some(sum.unwrap_or(0.0) + value)),
+    }
+}
+
+/// missing values are skipped, `None` if all of them are missing. Integers stop at their bounds.
+fn sum_values<T: ChartValue>(dataset: &Dataset<T>, cluster_indices: &[usize]) -> Option<T> {
+    cluster_indices
+        .iter()
+        .filter_map(|cluster_index| dataset.get_value(*cluster_index))
+        .fold(None, |sum, value| match sum {
+            Some(sum) => Some(ChartValue::saturating_add(sum, value)),
+            None => Some(value),
+        })
+}
@@ -1,12 +1,15 @@
 use crate::charts::axis_enums::{AxisOrientation, XAxisOrientation, YAxisOrientation};
 use crate::charts::bar_chart_style_info_iced::BarChartStyleInfoIced;
+use crate::charts::chart_value::ChartValue;
 use crate::charts::error::{ChartsLibError, ErrorKind};
+use crate::charts::fill::Fill;
+use crate::charts::scene::Scene;
 use crate::charts::util::{
     placeholder_get_max_text_height, placeholder_get_max_text_width, placeholder_get_text_height,
     placeholder_get_text_width,
 };
-use iced::canvas::{Frame, LineCap, LineJoin, Path, Stroke, Text};
-use iced::{Color, Point, Size, Space};
+use iced::{Color, Point, Rectangle, Size, Space};
+use serde::{Deserialize, Serialize};
 use std::borrow::Borrow;
 
 //TODO: make presets nicer
@@ -19,6 +22,8 @@ const AXIS_STROKE_WIDTH: f32 = 2.0;
 const X_TEXT_COLOR: Color = Color::BLACK;
 const Y_TEXT_COLOR: Color = Color::BLACK;
 const TEXT_SIZE: f32 = 16.0;
+const LEGEND_TEXT_COLOR: Color = Color::BLACK;
+const LEGEND_SWATCH_SIZE: f32 = 12.0;
 
 //TODO: make internal only, config through chart specific configs
 #[derive(Debug, Clone)]
@@ -42,11 +47,16 @@ pub struct CoordinateSystemConfig {
     pub axis_stroke_color: Color,
     pub axis_stroke_width: f32,
     pub legend_alignment: Option<CoordinateSystemLegendAlignment>,
+    pub legend_entries: Vec<LegendEntry>,
+    pub legend_text_color: Color,
+    pub legend_text_size: f32,
     pub text_padding: f32,
-    pub x_min_value: f32,
-    pub x_max_value: f32,
-    pub y_min_value: f32,
-    pub y_max_value: f32,
+    pub x_min_value: f64,
+    pub x_max_value: f64,
+    pub y_min_value: f64,
+    pub y_max_value: f64,
+    /// formats the markings of the value axis in the precision of the data
+    pub value_formatter: fn(f64) -> String,
     pub headline_size: f32,
 }
 
@@ -74,11 +84,15 @@ impl CoordinateSystemConfig {
             axis_stroke_color: AXIS_STROKE_COLOR,
             axis_stroke_width: AXIS_STROKE_WIDTH,
             legend_alignment: None,
+            legend_entries: vec![],
+            legend_text_color: LEGEND_TEXT_COLOR,
+            legend_text_size: TEXT_SIZE,
             text_padding: TEXT_PADDING,
             x_min_value: 0.0,
             x_max_value: 0.0,
             y_min_value: 0.0,
             y_max_value: 0.0,
+            value_formatter: <f32 as ChartValue>::format_axis_value,
             headline_size: TEXT_SIZE,
         }
     }
@@ -111,9 +125,23 @@ impl AxisAndOrigin {
             self.origin,
         )
     }
+
+    /// returns the area spanned by the axes
+    pub fn get_plot_area(&self) -> Rectangle {
+        Rectangle::new(
+            Point::new(
+                self.origin.x - self.x_axis_negative_length,
+                self.origin.y - self.y_axis_positive_length,
+            ),
+            Size::new(
+                self.x_axis_positive_length + self.x_axis_negative_length,
+                self.y_axis_positive_length + self.y_axis_negative_length,
+            ),
+        )
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum CoordinateSystemLegendAlignment {
     Top,
     Left,
@@ -121,6 +149,26 @@ pub enum CoordinateSystemLegendAlignment {
     Bottom,
 }
 
+/// One swatch with its label in the legend.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LegendEntry {
+    pub label: String,
+    pub fill: Fill,
+}
+
+impl LegendEntry {
+    pub fn new(label: String, color: Color) -> Self {
+        LegendEntry {
+            label,
+            fill: Fill::Solid(color),
+        }
+    }
+
+    pub fn with_fill(label: String, fill: Fill) -> Self {
+        LegendEntry { label, fill }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct SpaceCalculator {
     pub space_top: f32,
@@ -277,16 +325,17 @@ impl CoordinateSystem {
                 x_max_text_height = placeholder_get_max_text_height(x_markings_text);
                 x_max_text_width = placeholder_get_max_text_width(x_markings_text);
             } else {
+                let value_formatter = self.coordinate_system_config.value_formatter;
                 let x_max_value = self.coordinate_system_config.x_max_value;
                 let x_min_value = self.coordinate_system_config.x_min_value;
                 let x_max_value_text_height =
-                    placeholder_get_text_height(&Text::from(x_max_value.to_string().as_str()));
+                    placeholder_get_text_height(&value_formatter(x_max_value));
                 let x_min_value_text_height =
-                    placeholder_get_text_height(&Text::from(x_min_value.to_string().as_str()));
+                    placeholder_get_text_height(&value_formatter(x_min_value));
                 let x_max_value_text_width =
-                    placeholder_get_text_width(&Text::from(x_max_value.to_string().as_str()));
+                    placeholder_get_text_width(&value_formatter(x_max_value));
                 let x_min_value_text_width =
-                    placeholder_get_text_width(&Text::from(x_min_value.to_string().as_str()));
+                    placeholder_get_text_width(&value_formatter(x_min_value));
                 x_max_text_height = if x_max_value_text_height > x_min_value_text_height {
                     x_max_text_height
                 } else {
@@ -306,16 +355,17 @@ impl CoordinateSystem {
                 y_max_text_height = placeholder_get_max_text_height(y_markings_text);
                 y_max_text_width = placeholder_get_max_text_width(y_markings_text);
             } else {
+                let value_formatter = self.coordinate_system_config.value_formatter;
                 let y_max_value = self.coordinate_system_config.y_max_value;
                 let y_min_value = self.coordinate_system_config.y_min_value;
                 let y_max_value_text_height =
-                    placeholder_get_text_height(&Text::from(y_max_value.to_string().as_str()));
+                    placeholder_get_text_height(&value_formatter(y_max_value));
                 let y_min_value_text_height =
-                    placeholder_get_text_height(&Text::from(y_min_value.to_string().as_str()));
+                    placeholder_get_text_height(&value_formatter(y_min_value));
                 let y_max_value_text_width =
-                    placeholder_get_text_width(&Text::from(y_max_value.to_string().as_str()));
+                    placeholder_get_text_width(&value_formatter(y_max_value));
                 let y_min_value_text_width =
-                    placeholder_get_text_width(&Text::from(y_min_value.to_string().as_str()));
+                    placeholder_get_text_width(&value_formatter(y_min_value));
                 y_max_text_height = if y_max_value_text_height > y_min_value_text_height {
                     y_max_text_height
                 } else {
@@ -397,12 +447,80 @@ impl CoordinateSystem {
 
     fn calculate_headline_space(&self, space_calculator: &mut SpaceCalculator) {
         if let Some(headline_text) = &self.coordinate_system_config.headline {
-            space_calculator.space_top +=
-                placeholder_get_text_height(&Text::from(&*headline_text.as_str()));
+            space_calculator.space_top += placeholder_get_text_height(headline_text);
+        }
+    }
+
+    fn calculate_legend_space(&self, space_calculator: &mut SpaceCalculator) {
+        if let Some((legend_alignment, legend_size)) = self.get_legend_alignment_and_size() {
+            match legend_alignment {
+                CoordinateSystemLegendAlignment::Top => {
+                    space_calculator.space_top += legend_size.height
+                }
+                CoordinateSystemLegendAlignment::Bottom => {
+                    space_calculator.space_bottom += legend_size.height
+                }
+                CoordinateSystemLegendAlignment::Left => {
+                    space_calculator.space_left += legend_size.width
+                }
+                CoordinateSystemLegendAlignment::Right => {
+                    space_calculator.space_right += legend_size.width
+                }
+            }
         }
     }
-    // TODO create legends stuff
-    fn calculate_legend_space(&self, space_calculator: &mut SpaceCalculator) {}
+
+    /// returns the alignment and the size including padding of the legend, `None` if there is no legend to draw.
+    /// Top and bottom legends are one row, left and right legends one column.
+    fn get_legend_alignment_and_size(&self) -> Option<(CoordinateSystemLegendAlignment, Size)> {
+        let config = &self.coordinate_system_config;
+        let legend_alignment = config.legend_alignment?;
+        if config.legend_entries.is_empty() {
+            return None;
+        }
+        let padding = config.text_padding;
+        let row_height = self.get_legend_row_height();
+        let entry_amount = config.legend_entries.len() as f32;
+        let size = match legend_alignment {
+            CoordinateSystemLegendAlignment::Top | CoordinateSystemLegendAlignment::Bottom => {
+                let entries_width: f32 = config
+                    .legend_entries
+                    .iter()
+                    .map(|entry| self.get_legend_entry_width(entry))
+                    .sum();
+                Size::new(
+                    entries_width + padding * (entry_amount + 1.0),
+                    row_height + 2.0 * padding,
+                )
+            }
+            CoordinateSystemLegendAlignment::Left | CoordinateSystemLegendAlignment::Right => {
+                let max_entry_width = config
+                    .legend_entries
+                    .iter()
+                    .map(|entry| self.get_legend_entry_width(entry))
+                    .fold(0.0, f32::max);
+                Size::new(
+                    max_entry_width + 2.0 * padding,
+                    row_height * entry_amount + padding * (entry_amount + 1.0),
+                )
+            }
+        };
+        Some((legend_alignment, size))
+    }
+
+    fn get_legend_row_height(&self) -> f32 {
+        self.coordinate_system_config
+            .legend_entries
+            .iter()
+            .map(|entry| placeholder_get_text_height(&entry.label))
+            .fold(LEGEND_SWATCH_SIZE, f32::max)
+    }
+
+    fn get_legend_entry_width(&self, entry: &LegendEntry) -> f32 {
+        LEGEND_SWATCH_SIZE
+            + self.coordinate_system_config.text_padding
+            + placeholder_get_text_width(&entry.label)
+    }
 
     fn calculate_spacing(&self, size: Size) -> SpaceCalculator {
         let mut space_calculator = SpaceCalculator::new();
@@ -496,8 +614,8 @@ impl CoordinateSystem {
 
     fn create_axis_markings_texts_vector(
         &self,
-        start: f32,
-        end: f32,
+        start: f64,
+        end: f64,
         markings_amount: usize,
         negative: bool,
     ) -> Vec<String> {
@@ -507,18 +625,16 @@ impl CoordinateSystem {
         //calculate the step size and the text for it:
         let mut markings_text_vec: Vec<String> = Vec::with_capacity(markings_amount);
         let space_between_start_end = (start - end).abs();
-        let step = space_between_start_end / (markings_amount as f32 - 1.0);
+        let step = space_between_start_end / (markings_amount as f64 - 1.0);
         let mut current = start;
         while current <= end.abs() + step / 2.0 {
-            let mut tmp = current * 100.0;
-            tmp = tmp.round();
-            tmp /= 100.0;
+            let text = (self.coordinate_system_config.value_formatter)(current);
             if negative {
-                let mut inserter = tmp.to_string();
+                let mut inserter = text;
                 inserter.insert(0, '-');
                 markings_text_vec.push(inserter);
             } else {
-                markings_text_vec.push(tmp.to_string());
+                markings_text_vec.push(text);
             }
             current += step;
         }
@@ -526,13 +642,14 @@ impl CoordinateSystem {
     }
 
     // Drawing functions:
-    pub fn draw_headline_and_labels(&self, axis_and_origin: &AxisAndOrigin, frame: &mut Frame) {
-        self.draw_headline(frame);
-        self.draw_axis_makings_labels(axis_and_origin, frame);
-        self.draw_cluster_labels(axis_and_origin, frame);
+    pub fn draw_headline_and_labels(&self, axis_and_origin: &AxisAndOrigin, scene: &mut Scene) {
+        self.draw_headline(scene);
+        self.draw_legend(scene);
+        self.draw_axis_makings_labels(axis_and_origin, scene);
+        self.draw_cluster_labels(axis_and_origin, scene);
     }
 
-    pub fn draw_axis(&self, axis_and_origin: &AxisAndOrigin, frame: &mut Frame) {
+    pub fn draw_axis(&self, axis_and_origin: &AxisAndOrigin, scene: &mut Scene) {
         let (
             x_axis_positive_length,
             x_axis_negative_length,
@@ -548,42 +665,42 @@ impl CoordinateSystem {
 
         let stroke_width = self.coordinate_system_config.axis_stroke_width;
 
-        self.draw_axis_line(origin, x_positive_end, frame);
-        self.draw_axis_line(origin, x_negative_end, frame);
-        self.draw_axis_line(origin, y_positive_end, frame);
-        self.draw_axis_line(origin, y_negative_end, frame);
+        self.draw_axis_line(origin, x_positive_end, scene);
+        self.draw_axis_line(origin, x_negative_end, scene);
+        self.draw_axis_line(origin, y_positive_end, scene);
+        self.draw_axis_line(origin, y_negative_end, scene);
         let origin_square_top_left =
             Point::new(origin.x - stroke_width / 2.0, origin.y - stroke_width / 2.0);
-        frame.fill_rectangle(
+        scene.fill_rectangle(
             origin_square_top_left,
             Size::new(stroke_width, stroke_width),
             self.coordinate_system_config.axis_stroke_color,
         );
     }
 
-    fn draw_cluster_labels(&self, axis_and_origin: &AxisAndOrigin, frame: &mut Frame) {
-        self.draw_x_cluster_labels(&axis_and_origin, frame);
-        self.draw_y_cluster_labels(&axis_and_origin, frame);
+    fn draw_cluster_labels(&self, axis_and_origin: &AxisAndOrigin, scene: &mut Scene) {
+        self.draw_x_cluster_labels(&axis_and_origin, scene);
+        self.draw_y_cluster_labels(&axis_and_origin, scene);
     }
 
-    fn draw_axis_makings_labels(&self, axis_and_origin: &AxisAndOrigin, frame: &mut Frame) {
+    fn draw_axis_makings_labels(&self, axis_and_origin: &AxisAndOrigin, scene: &mut Scene) {
         if self
             .coordinate_system_config
             .draw_marking_label_on_x_axis
             .is_some()
         {
-            self.draw_x_axis_markings_labels(axis_and_origin, frame);
+            self.draw_x_axis_markings_labels(axis_and_origin, scene);
         }
         if self
             .coordinate_system_config
             .draw_marking_label_on_y_axis
             .is_some()
         {
-            self.draw_y_axis_markings_labels(axis_and_origin, frame);
+            self.draw_y_axis_markings_labels(axis_and_origin, scene);
         }
     }
 
-    fn draw_x_cluster_labels(&self, axis_and_origin: &AxisAndOrigin, frame: &mut Frame) {
+    fn draw_x_cluster_labels(&self, axis_and_origin: &AxisAndOrigin, scene: &mut Scene) {
         let (
             x_axis_positive_length,
             x_axis_negative_length,
@@ -617,7 +734,6 @@ impl CoordinateSystem {
                     current_position.y += y_axis_negative_length;
                 }
                 for text in x_clusters_texts {
-                    let text = Text::from(&*text.as_str());
                     let text_width = placeholder_get_text_width(&text);
 
                     self.draw_text_at_point(
@@ -626,22 +742,22 @@ impl CoordinateSystem {
                         current_position.y,
                         self.coordinate_system_config.x_text_color,
                         self.coordinate_system_config.x_text_size,
-                        frame,
+                        scene,
                     );
 
-                    self.draw_separator_line(separator_line_start, separator_line_end, frame);
+                    self.draw_separator_line(separator_line_start, separator_line_end, scene);
 
                     separator_line_start.x += step;
                     separator_line_end.x += step;
 
                     current_position.x += step;
                 }
-                self.draw_separator_line(separator_line_start, separator_line_end, frame);
+                self.draw_separator_line(separator_line_start, separator_line_end, scene);
             }
         }
     }
 
-    fn draw_y_cluster_labels(&self, axis_and_origin: &AxisAndOrigin, frame: &mut Frame) {
+    fn draw_y_cluster_labels(&self, axis_and_origin: &AxisAndOrigin, scene: &mut Scene) {
         let (
             x_axis_positive_length,
             x_axis_negative_length,
@@ -675,7 +791,6 @@ impl CoordinateSystem {
                     current_position.x -= x_axis_negative_length;
                 }
                 for text in y_clusters_texts {
-                    let text = Text::from(&*text.as_str());
                     let text_height = placeholder_get_text_height(&text);
 
                     self.draw_text_at_point(
@@ -684,65 +799,120 @@ impl CoordinateSystem {
                         current_position.y - step / 2.0 - text_height / 2.0,
                         self.coordinate_system_config.y_text_color,
                         self.coordinate_system_config.y_text_size,
-                        frame,
+                        scene,
                     );
 
-                    self.draw_separator_line(separator_line_start, separator_line_end, frame);
+                    self.draw_separator_line(separator_line_start, separator_line_end, scene);
 
                     separator_line_start.y -= step;
                     separator_line_end.y -= step;
 
                     current_position.y -= step;
                 }
-                self.draw_separator_line(separator_line_start, separator_line_end, frame);
+                self.draw_separator_line(separator_line_start, separator_line_end, scene);
             }
         }
     }
 
-    fn draw_headline(&self, frame: &mut Frame) {
+    fn draw_headline(&self, scene: &mut Scene) {
         if let Some(headline_text) = &self.coordinate_system_config.headline {
-            let text = Text::from(&*headline_text.as_str());
+            let text = headline_text.clone();
             let headline_length = placeholder_get_text_width(&text);
             let headline_offset = headline_length / 2.0;
 
             self.draw_text_at_point(
                 text,
-                frame.width() / 2.0 - headline_offset,
+                scene.width() / 2.0 - headline_offset,
                 0.0,
                 self.coordinate_system_config.headline_color,
                 self.coordinate_system_config.headline_size,
-                frame,
+                scene,
+            );
+        }
+    }
+
+    fn draw_legend(&self, scene: &mut Scene) {
+        let (legend_alignment, legend_size) = match self.get_legend_alignment_and_size() {
+            Some(alignment_and_size) => alignment_and_size,
+            None => return,
+        };
+        let config = &self.coordinate_system_config;
+        let padding = config.text_padding;
+        let headline_height = match &config.headline {
+            Some(headline_text) => placeholder_get_text_height(headline_text),
+            None => 0.0,
+        };
+        let top_left = match legend_alignment {
+            CoordinateSystemLegendAlignment::Top => {
+                Point::new((scene.width() - legend_size.width) / 2.0, headline_height)
+            }
+            CoordinateSystemLegendAlignment::Bottom => Point::new(
+                (scene.width() - legend_size.width) / 2.0,
+                scene.height() - legend_size.height,
+            ),
+            CoordinateSystemLegendAlignment::Left => Point::new(0.0, headline_height),
+            CoordinateSystemLegendAlignment::Right => {
+                Point::new(scene.width() - legend_size.width, headline_height)
+            }
+        };
+
+        let row_height = self.get_legend_row_height();
+        let mut position = Point::new(top_left.x + padding, top_left.y + padding);
+        for entry in &config.legend_entries {
+            scene.fill_rectangle_with(
+                Point::new(
+                    position.x,
+                    position.y + (row_height - LEGEND_SWATCH_SIZE) / 2.0,
+                ),
+                Size::new(LEGEND_SWATCH_SIZE, LEGEND_SWATCH_SIZE),
+                &entry.fill,
+            );
+            self.draw_text_at_point(
+                entry.label.clone(),
+                position.x + LEGEND_SWATCH_SIZE + padding,
+                position.y + (row_height - placeholder_get_text_height(&entry.label)) / 2.0,
+                config.legend_text_color,
+                config.legend_text_size,
+                scene,
             );
+            match legend_alignment {
+                CoordinateSystemLegendAlignment::Top | CoordinateSystemLegendAlignment::Bottom => {
+                    position.x += self.get_legend_entry_width(entry) + padding
+                }
+                CoordinateSystemLegendAlignment::Left | CoordinateSystemLegendAlignment::Right => {
+                    position.y += row_height + padding
+                }
+            }
         }
     }
 
-    fn draw_x_axis_markings_labels(&self, axis_and_origin: &AxisAndOrigin, frame: &mut Frame) {
+    fn draw_x_axis_markings_labels(&self, axis_and_origin: &AxisAndOrigin, scene: &mut Scene) {
         if let Some(markings_texts) = self.coordinate_system_config.x_text.as_ref() {
             match self.x_axis_orientation {
                 XAxisOrientation::Positive => {
                     self.draw_x_axis_positive_marking_labels(
                         markings_texts.clone(),
                         axis_and_origin,
-                        frame,
+                        scene,
                     );
                 }
                 XAxisOrientation::Negative => {
                     self.draw_x_axis_negative_marking_labels(
                         markings_texts.clone(),
                         axis_and_origin,
-                        frame,
+                        scene,
                     );
                 }
                 XAxisOrientation::Complete => {
                     self.draw_x_axis_positive_marking_labels(
                         markings_texts.clone(),
                         axis_and_origin,
-                        frame,
+                        scene,
                     );
                     self.draw_x_axis_negative_marking_labels(
                         markings_texts.clone(),
                         axis_and_origin,
-                        frame,
+                        scene,
                     );
                 }
             };
@@ -757,7 +927,7 @@ impl CoordinateSystem {
                             false,
                         ),
                         axis_and_origin,
-                        frame,
+                        scene,
                     );
                 }
                 XAxisOrientation::Negative => {
@@ -769,7 +939,7 @@ impl CoordinateSystem {
                             true,
                         ),
                         axis_and_origin,
-                        frame,
+                        scene,
                     );
                 }
                 XAxisOrientation::Complete => {
@@ -781,7 +951,7 @@ impl CoordinateSystem {
                             false,
                         ),
                         axis_and_origin,
-                        frame,
+                        scene,
                     );
                     self.draw_x_axis_negative_marking_labels(
                         self.create_axis_markings_texts_vector(
@@ -791,7 +961,7 @@ impl CoordinateSystem {
                             true,
                         ),
                         axis_and_origin,
-                        frame,
+                        scene,
                     );
                 }
             };
@@ -802,33 +972,33 @@ impl CoordinateSystem {
         }
     }
 
-    fn draw_y_axis_markings_labels(&self, axis_and_origin: &AxisAndOrigin, frame: &mut Frame) {
+    fn draw_y_axis_markings_labels(&self, axis_and_origin: &AxisAndOrigin, scene: &mut Scene) {
         if let Some(markings_texts) = self.coordinate_system_config.y_text.as_ref() {
             match self.y_axis_orientation {
                 YAxisOrientation::Positive => {
                     self.draw_y_axis_positive_marking_labels(
                         markings_texts.clone(),
                         axis_and_origin,
-                        frame,
+                        scene,
                     );
                 }
                 YAxisOrientation::Negative => {
                     self.draw_y_axis_negative_marking_labels(
                         markings_texts.clone(),
                         axis_and_origin,
-                        frame,
+                        scene,
                     );
                 }
                 YAxisOrientation::Complete => {
                     self.draw_y_axis_positive_marking_labels(
                         markings_texts.clone(),
                         axis_and_origin,
-                        frame,
+                        scene,
                     );
                     self.draw_y_axis_negative_marking_labels(
                         markings_texts.clone(),
                         axis_and_origin,
-                        frame,
+                        scene,
                     );
                 }
             };
@@ -843,7 +1013,7 @@ impl CoordinateSystem {
                             false,
                         ),
                         axis_and_origin,
-                        frame,
+                        scene,
                     );
                 }
                 YAxisOrientation::Negative => {
@@ -855,7 +1025,7 @@ impl CoordinateSystem {
                             true,
                         ),
                         axis_and_origin,
-                        frame,
+                        scene,
                     );
                 }
                 YAxisOrientation::Complete => {
@@ -867,7 +1037,7 @@ impl CoordinateSystem {
                             false,
                         ),
                         axis_and_origin,
-                        frame,
+                        scene,
                     );
                     self.draw_y_axis_negative_marking_labels(
                         self.create_axis_markings_texts_vector(
@@ -877,7 +1047,7 @@ impl CoordinateSystem {
                             true,
                         ),
                         axis_and_origin,
-                        frame,
+                        scene,
                     );
                 }
             };
@@ -892,7 +1062,7 @@ impl CoordinateSystem {
         &self,
         markings_texts: Vec<String>,
         axis_and_origin: &AxisAndOrigin,
-        frame: &mut Frame,
+        scene: &mut Scene,
     ) {
         let (
             x_axis_positive_length,
@@ -917,7 +1087,6 @@ impl CoordinateSystem {
             current_position.y -= placeholder_get_max_text_height(&*markings_texts);
         }
         for text in markings_texts {
-            let text = Text::from(&*text.as_str());
             let text_width = placeholder_get_text_width(&text);
 
             self.draw_text_at_point(
@@ -926,10 +1095,10 @@ impl CoordinateSystem {
                 current_position.y,
                 self.coordinate_system_config.x_text_color,
                 self.coordinate_system_config.x_text_size,
-                frame,
+                scene,
             );
 
-            self.draw_separator_line(separator_line_start, separator_line_end, frame);
+            self.draw_separator_line(separator_line_start, separator_line_end, scene);
 
             separator_line_start.x += step;
             separator_line_end.x += step;
@@ -942,7 +1111,7 @@ impl CoordinateSystem {
         &self,
         markings_texts: Vec<String>,
         axis_and_origin: &AxisAndOrigin,
-        frame: &mut Frame,
+        scene: &mut Scene,
     ) {
         let (
             x_axis_positive_length,
@@ -967,7 +1136,6 @@ impl CoordinateSystem {
             current_position.y -= placeholder_get_max_text_height(&*markings_texts);
         }
         for text in markings_texts {
-            let text = Text::from(&*text.as_str());
             let text_width = placeholder_get_text_width(&text);
 
             self.draw_text_at_point(
@@ -976,10 +1144,10 @@ impl CoordinateSystem {
                 current_position.y,
                 self.coordinate_system_config.x_text_color,
                 self.coordinate_system_config.x_text_size,
-                frame,
+                scene,
             );
 
-            self.draw_separator_line(separator_line_start, separator_line_end, frame);
+            self.draw_separator_line(separator_line_start, separator_line_end, scene);
 
             separator_line_start.x -= step;
             separator_line_end.x -= step;
@@ -992,7 +1160,7 @@ impl CoordinateSystem {
         &self,
         markings_texts: Vec<String>,
         axis_and_origin: &AxisAndOrigin,
-        frame: &mut Frame,
+        scene: &mut Scene,
     ) {
         let (
             x_axis_positive_length,
@@ -1020,7 +1188,6 @@ impl CoordinateSystem {
         }
 
         for text in markings_texts {
-            let text = Text::from(&*text.as_str());
             let text_height = placeholder_get_text_height(&text);
 
             self.draw_text_at_point(
@@ -1029,9 +1196,9 @@ impl CoordinateSystem {
                 current_position.y - text_height / 2.0,
                 self.coordinate_system_config.y_text_color,
                 self.coordinate_system_config.y_text_size,
-                frame,
+                scene,
             );
-            self.draw_separator_line(separator_line_start, separator_line_end, frame);
+            self.draw_separator_line(separator_line_start, separator_line_end, scene);
             current_position.y += step;
             separator_line_start.y += step;
             separator_line_end.y += step;
@@ -1042,7 +1209,7 @@ impl CoordinateSystem {
         &self,
         markings_texts: Vec<String>,
         axis_and_origin: &AxisAndOrigin,
-        frame: &mut Frame,
+        scene: &mut Scene,
     ) {
         let (
             x_axis_positive_length,
@@ -1069,7 +1236,6 @@ impl CoordinateSystem {
         }
 
         for text in markings_texts {
-            let text = Text::from(&*text.as_str());
             let text_height = placeholder_get_text_height(&text);
 
             self.draw_text_at_point(
@@ -1078,9 +1244,9 @@ impl CoordinateSystem {
                 current_position.y - text_height / 2.0,
                 self.coordinate_system_config.y_text_color,
                 self.coordinate_system_config.y_text_size,
-                frame,
+                scene,
             );
-            self.draw_separator_line(separator_line_start, separator_line_end, frame);
+            self.draw_separator_line(separator_line_start, separator_line_end, scene);
             current_position.y -= step;
             separator_line_start.y -= step;
             separator_line_end.y -= step;
@@ -1089,43 +1255,129 @@ impl CoordinateSystem {
 
     fn draw_text_at_point(
         &self,
-        mut text: Text,
+        text: String,
         top_left_x: f32,
         top_left_y: f32,
         color: Color,
         size: f32,
-        frame: &mut Frame,
+        scene: &mut Scene,
     ) {
-        text.position = Point::new(top_left_x, top_left_y);
-        text.color = color;
-        text.size = size;
-        frame.fill_text(text);
+        scene.fill_text(text, Point::new(top_left_x, top_left_y), color, size);
     }
 
-    fn draw_separator_line(&self, start: Point, end: Point, frame: &mut Frame) {
-        frame.stroke(
-            &Path::line(start, end),
-            Stroke {
-                color: self.coordinate_system_config.separator_stroke_color,
-                width: self.coordinate_system_config.separator_stroke_width,
-                line_cap: LineCap::Butt,
-                line_join: LineJoin::Round,
-            },
+    fn draw_separator_line(&self, start: Point, end: Point, scene: &mut Scene) {
+        scene.stroke_line(
+            start,
+            end,
+            self.coordinate_system_config.separator_stroke_color,
+            self.coordinate_system_config.separator_stroke_width,
         );
     }
 
-    fn draw_axis_line(&self, start: Point, end: Point, frame: &mut Frame) {
-        frame.stroke(
-            &Path::line(start, end),
-            Stroke {
-                color: self.coordinate_system_config.axis_stroke_color,
-                width: self.coordinate_system_config.axis_stroke_width,
-                line_cap: LineCap::Butt,
-                line_join: LineJoin::Round,
-            },
+    fn draw_axis_line(&self, start: Point, end: Point, scene: &mut Scene) {
+        scene.stroke_line(
+            start,
+            end,
+            self.coordinate_system_config.axis_stroke_color,
+            self.coordinate_system_config.axis_stroke_width,
         );
     }
 
+    /// returns the index and the area of the cluster label at the position along the cluster axis
+    pub fn get_cluster_label_cell_at(
+        &self,
+        axis_and_origin: &AxisAndOrigin,
+        position: Point,
+    ) -> Option<(usize, Rectangle)> {
+        let (
+            x_axis_positive_length,
+            x_axis_negative_length,
+            y_axis_positive_length,
+            y_axis_negative_length,
+            origin,
+        ) = axis_and_origin.get_axis_and_origin();
+
+        if self
+            .coordinate_system_config
+            .draw_cluster_label_on_x_axis
+            .is_some()
+        {
+            let x_clusters_texts = self.coordinate_system_config.x_text.as_ref()?;
+            if x_clusters_texts.is_empty() {
+                return None;
+            }
+            let amount = x_clusters_texts.len() as f32;
+            let (start_x, step) = match self.x_axis_orientation {
+                XAxisOrientation::Positive => (origin.x, x_axis_positive_length / amount),
+                XAxisOrientation::Negative => (
+                    origin.x - x_axis_negative_length,
+                    x_axis_negative_length / amount,
+                ),
+                XAxisOrientation::Complete => (
+                    origin.x - x_axis_negative_length,
+                    (x_axis_positive_length + x_axis_negative_length) / amount,
+                ),
+            };
+            let text_height = placeholder_get_max_text_height(x_clusters_texts);
+            let label_y = match self.y_axis_orientation {
+                YAxisOrientation::Positive => origin.y,
+                YAxisOrientation::Negative => origin.y - text_height,
+                YAxisOrientation::Complete => origin.y + y_axis_negative_length,
+            };
+            let index = ((position.x - start_x) / step).floor();
+            if step <= 0.0 || index < 0.0 || index >= amount {
+                return None;
+            }
+            Some((
+                index as usize,
+                Rectangle::new(
+                    Point::new(start_x + index * step, label_y),
+                    Size::new(step, text_height),
+                ),
+            ))
+        } else if self
+            .coordinate_system_config
+            .draw_cluster_label_on_y_axis
+            .is_some()
+        {
+            let y_clusters_texts = self.coordinate_system_config.y_text.as_ref()?;
+            if y_clusters_texts.is_empty() {
+                return None;
+            }
+            let amount = y_clusters_texts.len() as f32;
+            let (start_y, step) = match self.y_axis_orientation {
+                YAxisOrientation::Positive => (origin.y, y_axis_positive_length / amount),
+                YAxisOrientation::Negative => (
+                    origin.y + y_axis_negative_length,
+                    y_axis_negative_length / amount,
+                ),
+                YAxisOrientation::Complete => (
+                    origin.y + y_axis_negative_length,
+                    (y_axis_positive_length + y_axis_negative_length) / amount,
+                ),
+            };
+            let text_width = placeholder_get_max_text_width(y_clusters_texts);
+            let label_x = match self.x_axis_orientation {
+                XAxisOrientation::Positive => origin.x - text_width,
+                XAxisOrientation::Negative => origin.x,
+                XAxisOrientation::Complete => origin.x - text_width - x_axis_negative_length,
+            };
+            let index = ((start_y - position.y) / step).floor();
+            if step <= 0.0 || index < 0.0 || index >= amount {
+                return None;
+            }
+            Some((
+                index as usize,
+                Rectangle::new(
+                    Point::new(label_x, start_y - (index + 1.0) * step),
+                    Size::new(text_width, step),
+                ),
+            ))
+        } else {
+            None
+        }
+    }
+
     //TODO: implement
     fn draw_zero_at_origin() {
         unimplemented!()
@@ -10,7 +10,41 @@ pub struct ChartsLibError {
 #[derive(Debug, Clone, Copy)]
 pub enum ErrorKind {
     IncompatibleOrientationAndDataAxis,
+    /// A dataset has more or less values than there are cluster names.
     DatasetToAddIsNotTheSameLengthAsExistingDatasets,
+    DuplicateDatasetId {
+        id: u32,
+    },
+    /// NaN or an infinite value.
+    NonFiniteValue {
+        dataset_id: u32,
+        cluster_index: usize,
+    },
+    /// The chart has no datasets or no clusters.
+    EmptyData,
+    /// A new cluster needs exactly one value per dataset.
+    ClusterValueCountMismatch,
+    UnknownDatasetId {
+        id: u32,
+    },
+    ClusterIndexOutOfRange {
+        index: usize,
+    },
+    /// The new order of the datasets has to contain every dataset id exactly once.
+    InvalidDatasetOrder,
+    ExportFailed,
+    FontLoadingFailed,
+    /// The CSV couldn't be read, e.g. because rows differ in length.
+    CsvReadFailed,
+    /// The CSV is missing its header or data columns.
+    InvalidCsvStructure,
+    /// A value couldn't be parsed as a number, line and column start at 1.
+    InvalidCsvValue {
+        line: u64,
+        column: usize,
+    },
+    InvalidChartSpec,
+    InvalidTheme,
 }
 
 impl fmt::Display for ErrorKind {
@@ -0,0 +1,306 @@
+use crate::charts::palette::mix;
+use crate::charts::scene::SceneElement;
+use crate::charts::serde_color;
+use iced::{Color, Point, Size};
+use serde::{Deserialize, Serialize};
+
+/// distance between two hatch lines or dots
+pub const HATCH_SPACING: f32 = 8.0;
+pub const HATCH_LINE_WIDTH: f32 = 1.5;
+pub const DOT_SIZE: f32 = 3.0;
+/// amount of bands a gradient is split into by renderers without native gradients
+const GRADIENT_BANDS: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum GradientDirection {
+    /// from left to right
+    Horizontal,
+    /// from top to bottom
+    Vertical,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum HatchPattern {
+    /// lines from bottom left to top right
+    Diagonal,
+    Dots,
+    /// diagonal lines in both directions
+    CrossHatch,
+}
+
+/// How the area of a rectangle is painted. Hatches keep datasets distinguishable in grayscale.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Fill {
+    Solid(#[serde(with = "serde_color::color")] Color),
+    LinearGradient {
+        #[serde(with = "serde_color::color")]
+        start_color: Color,
+        #[serde(with = "serde_color::color")]
+        end_color: Color,
+        direction: GradientDirection,
+    },
+    /// `background_color` is drawn below the pattern, the pattern is see through if it is `None`
+    Hatch {
+        pattern: HatchPattern,
+        #[serde(with = "serde_color::color")]
+        color: Color,
+        #[serde(with = "serde_color::option_color")]
+        background_color: Option<Color>,
+    },
+}
+
+/// A solid part of a fill clipped to a polygon.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FillPiece {
+    Polygon {
+        points: Vec<Point>,
+        color: Color,
+    },
+    Line {
+        start: Point,
+        end: Point,
+        color: Color,
+        width: f32,
+    },
+}
+
+impl Fill {
+    /// Returns solid rectangles and lines covering the rectangle like the fill does.
+    /// Gradients become bands of solid color, hatch lines are clipped to the rectangle.
+    pub fn decompose(&self, top_left: Point, size: Size) -> Vec<SceneElement> {
+        match self {
+            Fill::Solid(color) => vec![SceneElement::Rectangle {
+                top_left,
+                size,
+                color: *color,
+            }],
+            Fill::LinearGradient {
+                start_color,
+                end_color,
+                direction,
+            } => decompose_gradient(top_left, size, *start_color, *end_color, *direction),
+            Fill::Hatch {
+                pattern,
+                color,
+                background_color,
+            } => {
+                let mut elements = vec![];
+                if let Some(background_color) = background_color {
+                    elements.push(SceneElement::Rectangle {
+                        top_left,
+                        size,
+                        color: *background_color,
+                    });
+                }
+                match pattern {
+                    HatchPattern::Diagonal => {
+                        decompose_diagonal_lines(top_left, size, *color, false, &mut elements)
+                    }
+                    HatchPattern::CrossHatch => {
+                        decompose_diagonal_lines(top_left, size, *color, false, &mut elements);
+                        decompose_diagonal_lines(top_left, size, *color, true, &mut elements);
+                    }
+                    HatchPattern::Dots => decompose_dots(top_left, size, *color, &mut elements),
+                }
+                elements
+            }
+        }
+    }
+
+    /// Returns `decompose` clipped to `clip_polygon`, which has to be convex and clockwise,
+    /// for renderers without clipping.
+    pub fn decompose_clipped(
+        &self,
+        top_left: Point,
+        size: Size,
+        clip_polygon: &[Point],
+    ) -> Vec<FillPiece> {
+        let mut pieces = vec![];
+        for element in self.decompose(top_left, size) {
+            match element {
+                SceneElement::Rectangle {
+                    top_left,
+                    size,
+                    color,
+                } => {
+                    let points = clip_polygon_to_rectangle(clip_polygon, top_left, size);
+                    if points.len() >= 3 {
+                        pieces.push(FillPiece::Polygon { points, color });
+                    }
+                }
+                SceneElement::Line {
+                    start,
+                    end,
+                    color,
+                    width,
+                } => {
+                    if let Some((start, end)) = clip_line_to_polygon(start, end, clip_polygon) {
+                        pieces.push(FillPiece::Line {
+                            start,
+                            end,
+                            color,
+                            width,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+        pieces
+    }
+}
+
+/// returns the part of the convex polygon inside the rectangle
+fn clip_polygon_to_rectangle(polygon: &[Point], top_left: Point, size: Size) -> Vec<Point> {
+    let right = top_left.x + size.width;
+    let bottom = top_left.y + size.height;
+    let points = clip_polygon_by_half_plane(polygon, |point| point.x - top_left.x);
+    let points = clip_polygon_by_half_plane(&points, |point| right - point.x);
+    let points = clip_polygon_by_half_plane(&points, |point| point.y - top_left.y);
+    clip_polygon_by_half_plane(&points, |point| bottom - point.y)
+}
+
+/// Sutherland-Hodgman step, `distance` being positive inside of the half plane
+fn clip_polygon_by_half_plane<F: Fn(Point) -> f32>(polygon: &[Point], distance: F) -> Vec<Point> {
+    let mut clipped = vec![];
+    for (index, current) in polygon.iter().enumerate() {
+        let next = polygon[(index + 1) % polygon.len()];
+        let current_distance = distance(*current);
+        let next_distance = distance(next);
+        if current_distance >= 0.0 {
+            clipped.push(*current);
+        }
+        if (current_distance >= 0.0) != (next_distance >= 0.0) {
+            let t = current_distance / (current_distance - next_distance);
+            clipped.push(Point::new(
+                current.x + (next.x - current.x) * t,
+                current.y + (next.y - current.y) * t,
+            ));
+        }
+    }
+    clipped
+}
+
+/// returns the part of the line inside the convex, clockwise polygon
+fn clip_line_to_polygon(start: Point, end: Point, polygon: &[Point]) -> Option<(Point, Point)> {
+    let (mut start, mut end) = (start, end);
+    for (index, corner) in polygon.iter().enumerate() {
+        let next = polygon[(index + 1) % polygon.len()];
+        let (edge_x, edge_y) = (next.x - corner.x, next.y - corner.y);
+        if edge_x == 0.0 && edge_y == 0.0 {
+            continue;
+        }
+        let distance = |point: Point| edge_x * (point.y - corner.y) - edge_y * (point.x - corner.x);
+        let start_distance = distance(start);
+        let end_distance = distance(end);
+        if start_distance < 0.0 && end_distance < 0.0 {
+            return None;
+        }
+        if (start_distance < 0.0) != (end_distance < 0.0) {
+            let t = start_distance / (start_distance - end_distance);
+            let intersection = Point::new(
+                start.x + (end.x - start.x) * t,
+                start.y + (end.y - start.y) * t,
+            );
+            if start_distance < 0.0 {
+                start = intersection;
+            } else {
+                end = intersection;
+            }
+        }
+    }
+    Some((start, end))
+}
+
+fn decompose_gradient(
+    top_left: Point,
+    size: Size,
+    start_color: Color,
+    end_color: Color,
+    direction: GradientDirection,
+) -> Vec<SceneElement> {
+    (0..GRADIENT_BANDS)
+        .map(|band| {
+            let band_start = band as f32 / GRADIENT_BANDS as f32;
+            let band_end = (band + 1) as f32 / GRADIENT_BANDS as f32;
+            let color = mix(start_color, end_color, (band_start + band_end) / 2.0);
+            let (band_top_left, band_size) = match direction {
+                GradientDirection::Horizontal => (
+                    Point::new(top_left.x + size.width * band_start, top_left.y),
+                    Size::new(size.width * (band_end - band_start), size.height),
+                ),
+                GradientDirection::Vertical => (
+                    Point::new(top_left.x, top_left.y + size.height * band_start),
+                    Size::new(size.width, size.height * (band_end - band_start)),
+                ),
+            };
+            SceneElement::Rectangle {
+                top_left: band_top_left,
+                size: band_size,
+                color,
+            }
+        })
+        .collect()
+}
+
+/// Adds the lines x + y = c (bottom left to top right) or, if `falling`, x - y = c
+/// every `HATCH_SPACING` pixels, clipped to the rectangle.
+fn decompose_diagonal_lines(
+    top_left: Point,
+    size: Size,
+    color: Color,
+    falling: bool,
+    elements: &mut Vec<SceneElement>,
+) {
+    let left = top_left.x;
+    let right = top_left.x + size.width;
+    let top = top_left.y;
+    let bottom = top_left.y + size.height;
+    // the patterns are aligned to the scene, so neighbouring bars line up
+    let (first, last) = if falling {
+        (left - bottom, right - top)
+    } else {
+        (left + top, right + bottom)
+    };
+    let mut c = (first / HATCH_SPACING).ceil() * HATCH_SPACING;
+    while c <= last {
+        let (start_x, end_x) = if falling {
+            ((c + top).max(left), (c + bottom).min(right))
+        } else {
+            ((c - bottom).max(left), (c - top).min(right))
+        };
+        if start_x < end_x {
+            let y_at = |x: f32| if falling { x - c } else { c - x };
+            elements.push(SceneElement::Line {
+                start: Point::new(start_x, y_at(start_x)),
+                end: Point::new(end_x, y_at(end_x)),
+                color,
+                width: HATCH_LINE_WIDTH,
+            });
+        }
+        c += HATCH_SPACING;
+    }
+}
+
+fn decompose_dots(top_left: Point, size: Size, color: Color, elements: &mut Vec<SceneElement>) {
+    let half_dot = DOT_SIZE / 2.0;
+    let mut y = (top_left.y / HATCH_SPACING).floor() * HATCH_SPACING + HATCH_SPACING / 2.0;
+    while y - half_dot < top_left.y + size.height {
+        let mut x = (top_left.x / HATCH_SPACING).floor() * HATCH_SPACING + HATCH_SPACING / 2.0;
+        while x - half_dot < top_left.x + size.width {
+            let dot_left = (x - half_dot).max(top_left.x);
+            let dot_top = (y - half_dot).max(top_left.y);
+            let dot_right = (x + half_dot).min(top_left.x + size.width);
+            let dot_bottom = (y + half_dot).min(top_left.y + size.height);
+            if dot_left < dot_right && dot_top < dot_bottom {
+                elements.push(SceneElement::Rectangle {
+                    top_left: Point::new(dot_left, dot_top),
+                    size: Size::new(dot_right - dot_left, dot_bottom - dot_top),
+                    color,
+                });
+            }
+            x += HATCH_SPACING;
+        }
+        y += HATCH_SPACING;
+    }
+}
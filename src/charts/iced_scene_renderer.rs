@@ -0,0 +1,135 @@
+use crate::charts::fill::FillPiece;
+use crate::charts::scene::{rounded_rectangle_points, Scene, SceneElement};
+use iced::canvas::{Frame, LineCap, LineJoin, Path, Stroke, Text};
+use iced::Point;
+
+pub fn draw_scene(scene: &Scene, frame: &mut Frame) {
+    for element in &scene.elements {
+        draw_element(element, frame);
+    }
+}
+
+fn draw_element(element: &SceneElement, frame: &mut Frame) {
+    match element {
+        SceneElement::Rectangle {
+            top_left,
+            size,
+            color,
+        } => {
+            frame.fill(&Path::rectangle(*top_left, *size), *color);
+        }
+        SceneElement::FilledRectangle {
+            top_left,
+            size,
+            fill,
+        } => {
+            for element in fill.decompose(*top_left, *size) {
+                draw_element(&element, frame);
+            }
+        }
+        SceneElement::RoundedRectangle {
+            top_left,
+            size,
+            radii,
+            fill,
+        } => {
+            let points = rounded_rectangle_points(*top_left, *size, *radii);
+            for piece in fill.decompose_clipped(*top_left, *size, &points) {
+                match piece {
+                    FillPiece::Polygon { points, color } => {
+                        frame.fill(&polygon_path(&points), color);
+                    }
+                    FillPiece::Line {
+                        start,
+                        end,
+                        color,
+                        width,
+                    } => {
+                        draw_element(
+                            &SceneElement::Line {
+                                start,
+                                end,
+                                color,
+                                width,
+                            },
+                            frame,
+                        );
+                    }
+                }
+            }
+        }
+        SceneElement::RoundedRectangleOutline {
+            top_left,
+            size,
+            radii,
+            color,
+            width,
+        } => {
+            frame.stroke(
+                &polygon_path(&rounded_rectangle_points(*top_left, *size, *radii)),
+                Stroke {
+                    color: *color,
+                    width: *width,
+                    line_cap: LineCap::Butt,
+                    line_join: LineJoin::Miter,
+                },
+            );
+        }
+        SceneElement::RectangleOutline {
+            top_left,
+            size,
+            color,
+            width,
+        } => {
+            frame.stroke(
+                &Path::rectangle(*top_left, *size),
+                Stroke {
+                    color: *color,
+                    width: *width,
+                    line_cap: LineCap::Butt,
+                    line_join: LineJoin::Miter,
+                },
+            );
+        }
+        SceneElement::Line {
+            start,
+            end,
+            color,
+            width,
+        } => {
+            frame.stroke(
+                &Path::line(*start, *end),
+                Stroke {
+                    color: *color,
+                    width: *width,
+                    line_cap: LineCap::Butt,
+                    line_join: LineJoin::Round,
+                },
+            );
+        }
+        SceneElement::Text {
+            content,
+            position,
+            color,
+            size,
+        } => {
+            let mut text = Text::from(content.as_str());
+            text.position = *position;
+            text.color = *color;
+            text.size = *size;
+            frame.fill_text(text);
+        }
+    }
+}
+
+fn polygon_path(points: &[Point]) -> Path {
+    Path::new(|builder| {
+        if let Some((first, rest)) = points.split_first() {
+            builder.move_to(*first);
+            for point in rest {
+                builder.line_to(*point);
+            }
+            builder.close();
+        }
+    })
+}
@@ -1,8 +1,25 @@
+pub mod aggregation;
+pub mod animation;
 pub mod axis_enums;
 pub mod bar_chart_data;
 pub mod bar_chart_iced_struct;
 pub mod bar_chart_style_info_iced;
+pub mod bar_color_rule;
+pub mod bar_hit_index;
+pub mod bar_selection;
+pub mod chart_spec;
+pub mod chart_value;
+pub mod cluster_sorting;
 pub mod coordinate_system;
 pub mod drawing_helper;
 pub mod error;
+pub mod fill;
+pub mod iced_scene_renderer;
+pub mod palette;
+pub mod pdf_renderer;
+pub mod png_renderer;
+pub mod scene;
+pub mod serde_color;
+pub mod svg_renderer;
+pub mod theme;
 pub mod util;
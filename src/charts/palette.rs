@@ -0,0 +1,225 @@
+use crate::charts::serde_color;
+use iced::Color;
+use serde::{Deserialize, Serialize};
+
+/// Qualitative palette taken from https://experience.sap.com/fiori-design-web/values-and-names/
+const SAP_FIORI: [u32; 11] = [
+    0x5899DA, 0xE8743B, 0x19A979, 0xED4A7B, 0x945ECF, 0x13A4B4, 0x525DF4, 0xBF399E, 0x6C8893,
+    0xEE6868, 0x2F6497,
+];
+/// https://jfly.uni-koeln.de/color/
+const OKABE_ITO: [u32; 8] = [
+    0xE69F00, 0x56B4E9, 0x009E73, 0xF0E442, 0x0072B2, 0xD55E00, 0xCC79A7, 0x000000,
+];
+/// https://personal.sron.nl/~pault/
+const TOL_BRIGHT: [u32; 7] = [
+    0x4477AA, 0xEE6677, 0x228833, 0xCCBB44, 0x66CCEE, 0xAA3377, 0xBBBBBB,
+];
+const TOL_MUTED: [u32; 9] = [
+    0xCC6677, 0x332288, 0xDDCC77, 0x117733, 0x88CCEE, 0x882255, 0x44AA99, 0x999933, 0xAA4499,
+];
+const TABLEAU_10: [u32; 10] = [
+    0x4E79A7, 0xF28E2B, 0xE15759, 0x76B7B2, 0x59A14F, 0xEDC948, 0xB07AA1, 0xFF9DA7, 0x9C755F,
+    0xBAB0AC,
+];
+
+/// Sequential and diverging ramps from https://colorbrewer2.org and matplotlib
+const BLUES: [u32; 5] = [0xF7FBFF, 0xC6DBEF, 0x6BAED6, 0x2171B5, 0x08306B];
+const GREENS: [u32; 5] = [0xF7FCF5, 0xC7E9C0, 0x74C476, 0x238B45, 0x00441B];
+const GREYS: [u32; 5] = [0xFFFFFF, 0xBDBDBD, 0x969696, 0x525252, 0x000000];
+const VIRIDIS: [u32; 5] = [0x440154, 0x3B528B, 0x21918C, 0x5EC962, 0xFDE725];
+const RED_BLUE: [u32; 7] = [
+    0xB2182B, 0xEF8A62, 0xFDDBC7, 0xF7F7F7, 0xD1E5F0, 0x67A9CF, 0x2166AC,
+];
+const BROWN_TEAL: [u32; 7] = [
+    0x8C510A, 0xD8B365, 0xF6E8C3, 0xF5F5F5, 0xC7EAE5, 0x5AB4AC, 0x01665E,
+];
+const PURPLE_GREEN: [u32; 7] = [
+    0x762A83, 0xAF8DC3, 0xE7D4E8, 0xF7F7F7, 0xD9F0D3, 0x7FBF7B, 0x1B7837,
+];
+
+/// how far each additional cycle through a palette moves towards white or black
+const SHADE_STEP: f32 = 0.3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QualitativePalette {
+    /// not colorblind safe
+    SapFiori,
+    /// colorblind safe
+    OkabeIto,
+    /// colorblind safe, the default palette of the bar chart
+    TolBright,
+    /// colorblind safe
+    TolMuted,
+    Tableau10,
+}
+
+impl Default for QualitativePalette {
+    fn default() -> Self {
+        QualitativePalette::TolBright
+    }
+}
+
+impl QualitativePalette {
+    pub fn colors(&self) -> Vec<Color> {
+        let hex_colors: &[u32] = match self {
+            QualitativePalette::SapFiori => &SAP_FIORI,
+            QualitativePalette::OkabeIto => &OKABE_ITO,
+            QualitativePalette::TolBright => &TOL_BRIGHT,
+            QualitativePalette::TolMuted => &TOL_MUTED,
+            QualitativePalette::Tableau10 => &TABLEAU_10,
+        };
+        hex_colors.iter().map(|hex| color_from_hex(*hex)).collect()
+    }
+
+    pub fn is_colorblind_safe(&self) -> bool {
+        match self {
+            QualitativePalette::OkabeIto
+            | QualitativePalette::TolBright
+            | QualitativePalette::TolMuted => true,
+            QualitativePalette::SapFiori | QualitativePalette::Tableau10 => false,
+        }
+    }
+}
+
+/// Ramps from light to dark (Viridis: dark to light), for values without a meaningful center.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SequentialPalette {
+    Blues,
+    Greens,
+    Greys,
+    Viridis,
+}
+
+impl SequentialPalette {
+    pub fn ramp(&self) -> ColorRamp {
+        let hex_colors: &[u32] = match self {
+            SequentialPalette::Blues => &BLUES,
+            SequentialPalette::Greens => &GREENS,
+            SequentialPalette::Greys => &GREYS,
+            SequentialPalette::Viridis => &VIRIDIS,
+        };
+        ColorRamp::new(hex_colors.iter().map(|hex| color_from_hex(*hex)).collect())
+    }
+}
+
+/// Ramps with a light center, for values deviating in both directions from a midpoint.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DivergingPalette {
+    RedBlue,
+    BrownTeal,
+    PurpleGreen,
+}
+
+impl DivergingPalette {
+    pub fn ramp(&self) -> ColorRamp {
+        let hex_colors: &[u32] = match self {
+            DivergingPalette::RedBlue => &RED_BLUE,
+            DivergingPalette::BrownTeal => &BROWN_TEAL,
+            DivergingPalette::PurpleGreen => &PURPLE_GREEN,
+        };
+        ColorRamp::new(hex_colors.iter().map(|hex| color_from_hex(*hex)).collect())
+    }
+}
+
+/// Evenly spaced color stops, colors in between are linearly interpolated.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColorRamp {
+    #[serde(with = "serde_color::color_vec")]
+    stops: Vec<Color>,
+}
+
+impl ColorRamp {
+    pub fn new(stops: Vec<Color>) -> Self {
+        ColorRamp { stops }
+    }
+
+    pub fn get_stops(&self) -> &[Color] {
+        &self.stops
+    }
+
+    /// returns the color at `position`, 0.0 being the first and 1.0 the last stop
+    pub fn color_at(&self, position: f32) -> Color {
+        match self.stops.len() {
+            0 => Color::BLACK,
+            1 => self.stops[0],
+            stop_amount => {
+                let position = if position.is_nan() {
+                    0.0
+                } else {
+                    position.clamp(0.0, 1.0)
+                };
+                let scaled_position = position * (stop_amount - 1) as f32;
+                let index = (scaled_position.floor() as usize).min(stop_amount - 2);
+                mix(
+                    self.stops[index],
+                    self.stops[index + 1],
+                    scaled_position - index as f32,
+                )
+            }
+        }
+    }
+
+    /// returns the color of `value` with `min` mapped to the first and `max` to the last stop
+    pub fn color_for_value(&self, value: f32, min: f32, max: f32) -> Color {
+        if max <= min {
+            self.color_at(0.5)
+        } else {
+            self.color_at((value - min) / (max - min))
+        }
+    }
+
+    /// returns `amount` evenly spaced colors, including the first and the last stop
+    pub fn sample(&self, amount: usize) -> Vec<Color> {
+        match amount {
+            0 => vec![],
+            1 => vec![self.color_at(0.5)],
+            _ => (0..amount)
+                .map(|index| self.color_at(index as f32 / (amount - 1) as f32))
+                .collect(),
+        }
+    }
+}
+
+/// Returns the palette color for `index`. Once the palette is exhausted it is repeated,
+/// alternately lighter and darker, so datasets stay distinguishable.
+pub fn get_cycled_color(palette: &[Color], index: usize) -> Color {
+    if palette.is_empty() {
+        return Color::BLACK;
+    }
+    let color = palette[index % palette.len()];
+    let cycle = index / palette.len();
+    if cycle == 0 {
+        return color;
+    }
+    // cycles 1 and 2 use the first shade step, 3 and 4 the second one and so on
+    let shade_level = cycle / 2 + cycle % 2;
+    let strength = 1.0 - (1.0 - SHADE_STEP).powi(shade_level as i32);
+    if cycle % 2 == 1 {
+        mix(color, Color::WHITE, strength)
+    } else {
+        mix(color, Color::BLACK, strength)
+    }
+}
+
+/// returns `amount` colors from the palette, generating shades if the palette is too short
+pub fn extend_palette(palette: &[Color], amount: usize) -> Vec<Color> {
+    (0..amount)
+        .map(|index| get_cycled_color(palette, index))
+        .collect()
+}
+
+/// returns the color for a hex value like `0x5899DA`
+pub fn color_from_hex(hex: u32) -> Color {
+    Color::from_rgb8((hex >> 16) as u8, (hex >> 8) as u8, hex as u8)
+}
+
+/// returns `first` moved towards `second` by `amount` from 0.0 to 1.0
+pub(crate) fn mix(first: Color, second: Color, amount: f32) -> Color {
+    Color::from_rgba(
+        first.r + (second.r - first.r) * amount,
+        first.g + (second.g - first.g) * amount,
+        first.b + (second.b - first.b) * amount,
+        first.a + (second.a - first.a) * amount,
+    )
+}
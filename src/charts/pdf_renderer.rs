@@ -0,0 +1,542 @@
+use crate::charts::error::{ChartsLibError, ErrorKind};
+use crate::charts::fill::Fill;
+use crate::charts::scene::{CornerRadii, Scene, SceneElement};
+use fontdue::{Font, FontSettings};
+use iced::{Color, Point, Size};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+/// distance of the bezier control points from a corner, relative to its radius, for a quarter circle
+const CIRCLE_KAPPA: f32 = 0.5523;
+/// glyph space units per text space unit, the font widths and metrics are given in them
+const GLYPH_SPACE_UNITS: f32 = 1000.0;
+/// a CMap allows at most 100 entries per `beginbfchar` block
+const CMAP_BLOCK_SIZE: usize = 100;
+/// name of the embedded font, viewers only use it to show which fonts a document contains
+const FONT_NAME: &str = "ChartFont";
+
+/// Turns a scene into a single page PDF document, one scene unit being one point.
+/// The whole font is embedded and text refers to its glyphs, so every character the font
+/// contains can be shown and the document doesn't depend on the fonts of the viewer.
+#[derive(Debug)]
+pub struct PdfRenderer {
+    font_data: Vec<u8>,
+    font: Font,
+}
+
+/// the graphics states and glyphs a content stream uses
+#[derive(Debug, Default)]
+struct PageResources {
+    /// formatted alpha values, the index being the name of the graphics state (`/GS<index>`)
+    alpha_values: Vec<String>,
+    /// the used glyphs with the character they show
+    glyphs: BTreeMap<u16, char>,
+}
+
+impl PdfRenderer {
+    /// Loads the font embedded for text, `font_data` being the content of a TrueType or OpenType
+    /// file like for `PngRenderer`. Font collections can't be embedded.
+    pub fn new(font_data: &[u8]) -> Result<Self, ChartsLibError> {
+        if font_data.starts_with(b"ttcf") {
+            return Err(ChartsLibError::new(
+                ErrorKind::FontLoadingFailed,
+                String::from("A font collection can't be embedded into a PDF."),
+            ));
+        }
+        let font = Font::from_bytes(font_data, FontSettings::default()).map_err(|error| {
+            ChartsLibError::new(
+                ErrorKind::FontLoadingFailed,
+                format!("Could not load the font: {}", error),
+            )
+        })?;
+        Ok(PdfRenderer {
+            font_data: font_data.to_vec(),
+            font,
+        })
+    }
+
+    /// Returns the scene as PDF document. Elements with numbers that aren't finite are left
+    /// out, the page size has to be finite.
+    pub fn render_pdf(&self, scene: &Scene) -> Result<Vec<u8>, ChartsLibError> {
+        let (width, height) = match (format_number(scene.width()), format_number(scene.height())) {
+            (Some(width), Some(height)) => (width, height),
+            _ => {
+                return Err(ChartsLibError::new(
+                    ErrorKind::ExportFailed,
+                    format!(
+                        "The page size {} x {} isn't finite.",
+                        scene.width(),
+                        scene.height()
+                    ),
+                ))
+            }
+        };
+        let mut resources = PageResources::default();
+        let content = self.create_content_stream(scene, &mut resources);
+
+        let mut ext_g_states = String::new();
+        for (index, alpha) in resources.alpha_values.iter().enumerate() {
+            let _ = write!(
+                ext_g_states,
+                "/GS{} << /Type /ExtGState /ca {} /CA {} >> ",
+                index, alpha, alpha
+            );
+        }
+        let font_resource = if resources.glyphs.is_empty() {
+            ""
+        } else {
+            "/Font << /F1 5 0 R >> "
+        };
+
+        let mut objects: Vec<Vec<u8>> = vec![
+            b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+            b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_vec(),
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Contents 4 0 R \
+                 /Resources << {}/ExtGState << {}>> >> >>",
+                width, height, font_resource, ext_g_states
+            )
+            .into_bytes(),
+            format!(
+                "<< /Length {} >>\nstream\n{}endstream",
+                content.len(),
+                content
+            )
+            .into_bytes(),
+        ];
+        if !resources.glyphs.is_empty() {
+            objects.extend(self.create_font_objects(&resources.glyphs));
+        }
+
+        // the comment with bytes above 127 tells tools that the file contains binary data
+        let mut pdf: Vec<u8> = b"%PDF-1.6\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets = vec![];
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.extend_from_slice(format!("{} 0 obj\n", index + 1).as_bytes());
+            pdf.extend_from_slice(object);
+            pdf.extend_from_slice(b"\nendobj\n");
+        }
+        let xref_offset = pdf.len();
+        let mut trailer = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            let _ = writeln!(trailer, "{:010} 00000 n ", offset);
+        }
+        let _ = write!(
+            trailer,
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref_offset
+        );
+        pdf.extend_from_slice(trailer.as_bytes());
+        Ok(pdf)
+    }
+
+    pub fn save_pdf<P: AsRef<Path>>(&self, scene: &Scene, path: P) -> Result<(), ChartsLibError> {
+        let pdf_data = self.render_pdf(scene)?;
+        fs::write(path.as_ref(), pdf_data).map_err(|error| {
+            ChartsLibError::new(
+                ErrorKind::ExportFailed,
+                format!(
+                    "Could not write the PDF to {}: {}",
+                    path.as_ref().display(),
+                    error
+                ),
+            )
+        })
+    }
+
+    /// Returns the objects 5 to 9: the composite font, its descendant font, the font descriptor,
+    /// the embedded font program and the map back to unicode used for copying text.
+    /// Character codes are glyph indices, so no encoding of the font is needed.
+    fn create_font_objects(&self, glyphs: &BTreeMap<u16, char>) -> Vec<Vec<u8>> {
+        let mut widths = String::new();
+        let (mut left, mut bottom, mut right, mut top) = (0, 0, 0, 0);
+        for glyph in glyphs.keys() {
+            let metrics = self.font.metrics_indexed(*glyph, GLYPH_SPACE_UNITS);
+            let _ = write!(
+                widths,
+                "{} [{}] ",
+                glyph,
+                metrics.advance_width.round() as i32
+            );
+            left = left.min(metrics.xmin);
+            bottom = bottom.min(metrics.ymin);
+            right = right.max(metrics.xmin + metrics.width as i32);
+            top = top.max(metrics.ymin + metrics.height as i32);
+        }
+        let (ascent, descent) = match self.font.horizontal_line_metrics(GLYPH_SPACE_UNITS) {
+            Some(line_metrics) => (
+                line_metrics.ascent.round() as i32,
+                line_metrics.descent.round() as i32,
+            ),
+            None => (top, bottom),
+        };
+
+        // OpenType fonts with CFF outlines start with `OTTO`, all others have TrueType outlines
+        let is_cff = self.font_data.starts_with(b"OTTO");
+        let (cid_font_type, cid_to_gid_map, font_file) = if is_cff {
+            ("CIDFontType0", "", "FontFile3")
+        } else {
+            ("CIDFontType2", " /CIDToGIDMap /Identity", "FontFile2")
+        };
+        let font_file_dictionary = if is_cff {
+            format!("<< /Length {} /Subtype /OpenType >>", self.font_data.len())
+        } else {
+            format!(
+                "<< /Length {} /Length1 {} >>",
+                self.font_data.len(),
+                self.font_data.len()
+            )
+        };
+        let mut font_file_object = format!("{}\nstream\n", font_file_dictionary).into_bytes();
+        font_file_object.extend_from_slice(&self.font_data);
+        font_file_object.extend_from_slice(b"\nendstream");
+
+        let to_unicode = create_to_unicode_cmap(glyphs);
+        vec![
+            format!(
+                "<< /Type /Font /Subtype /Type0 /BaseFont /{} /Encoding /Identity-H \
+                 /DescendantFonts [6 0 R] /ToUnicode 9 0 R >>",
+                FONT_NAME
+            )
+            .into_bytes(),
+            format!(
+                "<< /Type /Font /Subtype /{} /BaseFont /{} \
+                 /CIDSystemInfo << /Registry (Adobe) /Ordering (Identity) /Supplement 0 >> \
+                 /FontDescriptor 7 0 R /DW 0 /W [{}]{} >>",
+                cid_font_type, FONT_NAME, widths, cid_to_gid_map
+            )
+            .into_bytes(),
+            format!(
+                "<< /Type /FontDescriptor /FontName /{} /Flags 32 /FontBBox [{} {} {} {}] \
+                 /ItalicAngle 0 /Ascent {} /Descent {} /CapHeight {} /StemV 80 /{} 8 0 R >>",
+                FONT_NAME, left, bottom, right, top, ascent, descent, ascent, font_file
+            )
+            .into_bytes(),
+            font_file_object,
+            format!(
+                "<< /Length {} >>\nstream\n{}endstream",
+                to_unicode.len(),
+                to_unicode
+            )
+            .into_bytes(),
+        ]
+    }
+
+    /// Returns the drawing operators of the page, the graphics states and glyphs they use are
+    /// added to `resources`.
+    fn create_content_stream(&self, scene: &Scene, resources: &mut PageResources) -> String {
+        let height = scene.height();
+        let mut content = String::new();
+        for element in &scene.elements {
+            self.write_element(&mut content, element, height, resources);
+        }
+        content
+    }
+
+    /// appends the operators drawing `element` on a page of the given height, if all of its numbers are finite
+    fn write_element(
+        &self,
+        content: &mut String,
+        element: &SceneElement,
+        height: f32,
+        resources: &mut PageResources,
+    ) {
+        if let Some(operators) = self.format_element(element, height, resources) {
+            content.push_str("q\n");
+            content.push_str(&operators);
+            content.push_str("Q\n");
+        }
+    }
+
+    /// returns the operators drawing `element`, `None` if one of its numbers can't be written
+    fn format_element(
+        &self,
+        element: &SceneElement,
+        height: f32,
+        resources: &mut PageResources,
+    ) -> Option<String> {
+        let mut content = String::new();
+        let _ = match element {
+            SceneElement::Rectangle {
+                top_left,
+                size,
+                color,
+            } => writeln!(
+                content,
+                "{}{} rg\n{} re f",
+                set_alpha(color.a, resources)?,
+                format_color(*color)?,
+                format_rectangle(*top_left, *size, height)?
+            ),
+            SceneElement::FilledRectangle {
+                top_left,
+                size,
+                fill,
+            } => {
+                for element in fill.decompose(*top_left, *size) {
+                    self.write_element(&mut content, &element, height, resources);
+                }
+                Ok(())
+            }
+            SceneElement::RoundedRectangle {
+                top_left,
+                size,
+                radii,
+                fill,
+            } => {
+                let path = format_rounded_rectangle_path(*top_left, *size, *radii, height)?;
+                match fill {
+                    Fill::Solid(color) => writeln!(
+                        content,
+                        "{}{} rg\n{} f",
+                        set_alpha(color.a, resources)?,
+                        format_color(*color)?,
+                        path
+                    ),
+                    _ => {
+                        // the path clips the plain fill
+                        let _ = writeln!(content, "{} W n", path);
+                        for element in fill.decompose(*top_left, *size) {
+                            self.write_element(&mut content, &element, height, resources);
+                        }
+                        Ok(())
+                    }
+                }
+            }
+            SceneElement::RoundedRectangleOutline {
+                top_left,
+                size,
+                radii,
+                color,
+                width,
+            } => writeln!(
+                content,
+                "{}{} RG\n{} w 0 J 0 j\n{} S",
+                set_alpha(color.a, resources)?,
+                format_color(*color)?,
+                format_number(*width)?,
+                format_rounded_rectangle_path(*top_left, *size, *radii, height)?
+            ),
+            SceneElement::RectangleOutline {
+                top_left,
+                size,
+                color,
+                width,
+            } => writeln!(
+                content,
+                "{}{} RG\n{} w 0 J 0 j\n{} re S",
+                set_alpha(color.a, resources)?,
+                format_color(*color)?,
+                format_number(*width)?,
+                format_rectangle(*top_left, *size, height)?
+            ),
+            SceneElement::Line {
+                start,
+                end,
+                color,
+                width,
+            } => writeln!(
+                content,
+                "{}{} RG\n{} w 0 J 1 j\n{} {} m {} {} l S",
+                set_alpha(color.a, resources)?,
+                format_color(*color)?,
+                format_number(*width)?,
+                format_number(start.x)?,
+                format_number(height - start.y)?,
+                format_number(end.x)?,
+                format_number(height - end.y)?
+            ),
+            SceneElement::Text {
+                content: text,
+                position,
+                color,
+                size,
+            } => {
+                // the baseline is below the top of the text by the ascent, like in `PngRenderer`
+                let ascent = match self.font.horizontal_line_metrics(*size) {
+                    Some(line_metrics) => line_metrics.ascent,
+                    None => *size,
+                };
+                writeln!(
+                    content,
+                    "{}{} rg\nBT /F1 {} Tf {} {} Td {} Tj ET",
+                    set_alpha(color.a, resources)?,
+                    format_color(*color)?,
+                    format_number(*size)?,
+                    format_number(position.x)?,
+                    format_number(height - position.y - ascent)?,
+                    self.encode_text(text, resources)
+                )
+            }
+        };
+        Some(content)
+    }
+
+    /// Returns the text as hex string of glyph indices, characters missing in the font show
+    /// its missing glyph.
+    fn encode_text(&self, text: &str, resources: &mut PageResources) -> String {
+        let mut encoded = String::from("<");
+        for character in text.chars() {
+            let glyph = self.font.lookup_glyph_index(character);
+            resources.glyphs.entry(glyph).or_insert(character);
+            let _ = write!(encoded, "{:04X}", glyph);
+        }
+        encoded.push('>');
+        encoded
+    }
+}
+
+/// Returns the CMap mapping the glyph indices back to the characters they show, so text can be
+/// copied and searched. The missing glyph isn't mapped.
+fn create_to_unicode_cmap(glyphs: &BTreeMap<u16, char>) -> String {
+    let mut cmap = String::from(
+        "/CIDInit /ProcSet findresource begin\n12 dict begin\nbegincmap\n\
+         /CIDSystemInfo << /Registry (Adobe) /Ordering (UCS) /Supplement 0 >> def\n\
+         /CMapName /Adobe-Identity-UCS def\n/CMapType 2 def\n\
+         1 begincodespacerange\n<0000> <FFFF>\nendcodespacerange\n",
+    );
+    let mapped_glyphs: Vec<(&u16, &char)> =
+        glyphs.iter().filter(|(glyph, _)| **glyph != 0).collect();
+    for block in mapped_glyphs.chunks(CMAP_BLOCK_SIZE) {
+        let _ = writeln!(cmap, "{} beginbfchar", block.len());
+        for (glyph, character) in block {
+            let mut utf16 = [0; 2];
+            let _ = write!(cmap, "<{:04X}> <", glyph);
+            for unit in character.encode_utf16(&mut utf16) {
+                let _ = write!(cmap, "{:04X}", unit);
+            }
+            cmap.push_str(">\n");
+        }
+        cmap.push_str("endbfchar\n");
+    }
+    cmap.push_str("endcmap\nCMapName currentdict /CMap defineresource pop\nend\nend\n");
+    cmap
+}
+
+/// Returns the operator selecting the graphics state of a translucent alpha, empty for opaque
+/// colors. `None` if the alpha isn't finite.
+fn set_alpha(alpha: f32, resources: &mut PageResources) -> Option<String> {
+    let formatted_alpha = format_number(alpha)?;
+    if alpha >= 1.0 {
+        return Some(String::new());
+    }
+    let index = match resources
+        .alpha_values
+        .iter()
+        .position(|value| *value == formatted_alpha)
+    {
+        Some(index) => index,
+        None => {
+            resources.alpha_values.push(formatted_alpha);
+            resources.alpha_values.len() - 1
+        }
+    };
+    Some(format!("/GS{} gs\n", index))
+}
+
+/// returns the rectangle as `x y width height` in PDF coordinates, which start at the bottom
+fn format_rectangle(top_left: Point, size: Size, page_height: f32) -> Option<String> {
+    Some(format!(
+        "{} {} {} {}",
+        format_number(top_left.x)?,
+        format_number(page_height - top_left.y - size.height)?,
+        format_number(size.width)?,
+        format_number(size.height)?
+    ))
+}
+
+/// returns the path operators of the rectangle, corners being bezier approximated quarter circles
+fn format_rounded_rectangle_path(
+    top_left: Point,
+    size: Size,
+    radii: CornerRadii,
+    page_height: f32,
+) -> Option<String> {
+    let left = top_left.x;
+    let right = top_left.x + size.width;
+    let top = top_left.y;
+    let bottom = top_left.y + size.height;
+    let point = |x: f32, y: f32| {
+        Some(format!(
+            "{} {}",
+            format_number(x)?,
+            format_number(page_height - y)?
+        ))
+    };
+    let mut path = format!("{} m", point(left + radii.top_left, top)?);
+    // (corner, start of the curve, end of the curve) clockwise, the curve is skipped for square corners
+    let corners = [
+        (
+            (right, top),
+            (right - radii.top_right, top),
+            (right, top + radii.top_right),
+            radii.top_right,
+        ),
+        (
+            (right, bottom),
+            (right, bottom - radii.bottom_right),
+            (right - radii.bottom_right, bottom),
+            radii.bottom_right,
+        ),
+        (
+            (left, bottom),
+            (left + radii.bottom_left, bottom),
+            (left, bottom - radii.bottom_left),
+            radii.bottom_left,
+        ),
+        (
+            (left, top),
+            (left, top + radii.top_left),
+            (left + radii.top_left, top),
+            radii.top_left,
+        ),
+    ];
+    for (corner, start, end, radius) in corners.iter() {
+        let _ = write!(path, " {} l", point(start.0, start.1)?);
+        if *radius > 0.0 {
+            let control = |from: (f32, f32)| {
+                point(
+                    from.0 + (corner.0 - from.0) * CIRCLE_KAPPA,
+                    from.1 + (corner.1 - from.1) * CIRCLE_KAPPA,
+                )
+            };
+            let _ = write!(
+                path,
+                " {} {} {} c",
+                control(*start)?,
+                control(*end)?,
+                point(end.0, end.1)?
+            );
+        }
+    }
+    path.push_str(" h");
+    Some(path)
+}
+
+fn format_color(color: Color) -> Option<String> {
+    Some(format!(
+        "{} {} {}",
+        format_number(color.r)?,
+        format_number(color.g)?,
+        format_number(color.b)?
+    ))
+}
+
+/// PDF doesn't allow exponents, so numbers are always written with a fixed amount of decimals.
+/// Returns `None` for NaN and infinite numbers, which PDF can't express.
+fn format_number(number: f32) -> Option<String> {
+    if !number.is_finite() {
+        return None;
+    }
+    let formatted = format!("{:.3}", number);
+    let formatted = formatted.trim_end_matches('0').trim_end_matches('.');
+    if formatted == "-0" {
+        Some(String::from("0"))
+    } else {
+        Some(String::from(formatted))
+    }
+}
@@ -0,0 +1,528 @@
+use crate::charts::error::{ChartsLibError, ErrorKind};
+use crate::charts::fill::FillPiece;
+use crate::charts::scene::{rounded_rectangle_points, CornerRadii, Scene, SceneElement};
+use fontdue::{Font, FontSettings};
+use iced::{Color, Point, Size};
+use std::cmp::Ordering;
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+
+/// sub scanlines per pixel row when filling polygons
+const POLYGON_SAMPLES: usize = 4;
+
+/// CPU only renderer, turning a scene into an RGBA image or a PNG file without a window or GPU.
+/// The font for text has to be provided, since no system fonts are looked up.
+#[derive(Debug)]
+pub struct PngRenderer {
+    /// pixels per scene unit, e.g. 2.0 for a high DPI image
+    pub scale_factor: f32,
+    /// fills the whole image before drawing, the image is transparent if `None`
+    pub background_color: Option<Color>,
+    font: Font,
+}
+
+impl PngRenderer {
+    /// Loads the font used for text, `font_data` being the content of a TrueType or OpenType file.
+    pub fn new(font_data: &[u8]) -> Result<Self, ChartsLibError> {
+        let font = Font::from_bytes(font_data, FontSettings::default()).map_err(|error| {
+            ChartsLibError::new(
+                ErrorKind::FontLoadingFailed,
+                format!("Could not load the font: {}", error),
+            )
+        })?;
+        Ok(PngRenderer {
+            scale_factor: 1.0,
+            background_color: None,
+            font,
+        })
+    }
+
+    /// returns (width, height, pixels) with the pixels as non premultiplied RGBA, row by row
+    pub fn render_rgba(&self, scene: &Scene) -> (u32, u32, Vec<u8>) {
+        let mut pixmap = Pixmap::new(Size::new(
+            scene.width() * self.scale_factor,
+            scene.height() * self.scale_factor,
+        ));
+        if let Some(background_color) = self.background_color {
+            pixmap.fill_rectangle(
+                Point::ORIGIN,
+                Size::new(pixmap.width as f32, pixmap.height as f32),
+                background_color,
+            );
+        }
+        for element in &scene.elements {
+            self.draw_element(&mut pixmap, element);
+        }
+        (pixmap.width, pixmap.height, pixmap.into_rgba8())
+    }
+
+    fn draw_element(&self, pixmap: &mut Pixmap, element: &SceneElement) {
+        let scale = self.scale_factor;
+        match element {
+            SceneElement::Rectangle {
+                top_left,
+                size,
+                color,
+            } => {
+                pixmap.fill_rectangle(
+                    scale_point(*top_left, scale),
+                    size_scaled(*size, scale),
+                    *color,
+                );
+            }
+            SceneElement::FilledRectangle {
+                top_left,
+                size,
+                fill,
+            } => {
+                for element in fill.decompose(*top_left, *size) {
+                    self.draw_element(pixmap, &element);
+                }
+            }
+            SceneElement::RoundedRectangle {
+                top_left,
+                size,
+                radii,
+                fill,
+            } => {
+                let points = rounded_rectangle_points(*top_left, *size, *radii);
+                for piece in fill.decompose_clipped(*top_left, *size, &points) {
+                    match piece {
+                        FillPiece::Polygon { points, color } => {
+                            let points: Vec<Point> = points
+                                .into_iter()
+                                .map(|point| scale_point(point, scale))
+                                .collect();
+                            pixmap.fill_polygons(&[points], color);
+                        }
+                        FillPiece::Line {
+                            start,
+                            end,
+                            color,
+                            width,
+                        } => pixmap.stroke_line(
+                            scale_point(start, scale),
+                            scale_point(end, scale),
+                            color,
+                            width * scale,
+                        ),
+                    }
+                }
+            }
+            SceneElement::RoundedRectangleOutline {
+                top_left,
+                size,
+                radii,
+                color,
+                width,
+            } => {
+                pixmap.stroke_rounded_rectangle(
+                    scale_point(*top_left, scale),
+                    size_scaled(*size, scale),
+                    radii_scaled(*radii, scale),
+                    *color,
+                    width * scale,
+                );
+            }
+            SceneElement::RectangleOutline {
+                top_left,
+                size,
+                color,
+                width,
+            } => {
+                pixmap.stroke_rectangle(
+                    scale_point(*top_left, scale),
+                    size_scaled(*size, scale),
+                    *color,
+                    width * scale,
+                );
+            }
+            SceneElement::Line {
+                start,
+                end,
+                color,
+                width,
+            } => {
+                pixmap.stroke_line(
+                    scale_point(*start, scale),
+                    scale_point(*end, scale),
+                    *color,
+                    width * scale,
+                );
+            }
+            SceneElement::Text {
+                content,
+                position,
+                color,
+                size,
+            } => {
+                pixmap.fill_text(
+                    &self.font,
+                    content,
+                    scale_point(*position, scale),
+                    *color,
+                    size * scale,
+                );
+            }
+        }
+    }
+
+    /// returns the scene encoded as PNG
+    pub fn render_png(&self, scene: &Scene) -> Result<Vec<u8>, ChartsLibError> {
+        let (width, height, pixels) = self.render_rgba(scene);
+        let mut png_data = vec![];
+        {
+            let mut encoder = png::Encoder::new(&mut png_data, width, height);
+            encoder.set_color(png::ColorType::RGBA);
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder
+                .write_header()
+                .and_then(|mut writer| writer.write_image_data(&pixels))
+                .map_err(|error| {
+                    ChartsLibError::new(
+                        ErrorKind::ExportFailed,
+                        format!("Could not encode the PNG: {}", error),
+                    )
+                })?;
+        }
+        Ok(png_data)
+    }
+
+    pub fn save_png<P: AsRef<Path>>(&self, scene: &Scene, path: P) -> Result<(), ChartsLibError> {
+        let png_data = self.render_png(scene)?;
+        fs::write(path.as_ref(), png_data).map_err(|error| {
+            ChartsLibError::new(
+                ErrorKind::ExportFailed,
+                format!(
+                    "Could not write the PNG to {}: {}",
+                    path.as_ref().display(),
+                    error
+                ),
+            )
+        })
+    }
+}
+
+fn scale_point(point: Point, scale: f32) -> Point {
+    Point::new(point.x * scale, point.y * scale)
+}
+
+fn size_scaled(size: Size, scale: f32) -> Size {
+    Size::new(size.width * scale, size.height * scale)
+}
+
+fn radii_scaled(radii: CornerRadii, scale: f32) -> CornerRadii {
+    CornerRadii::new(
+        radii.top_left * scale,
+        radii.top_right * scale,
+        radii.bottom_right * scale,
+        radii.bottom_left * scale,
+    )
+}
+
+/// Premultiplied RGBA pixels, shapes are anti aliased by their pixel coverage.
+struct Pixmap {
+    width: u32,
+    height: u32,
+    pixels: Vec<[f32; 4]>,
+}
+
+impl Pixmap {
+    fn new(size: Size) -> Self {
+        let width = size.width.ceil().max(0.0) as u32;
+        let height = size.height.ceil().max(0.0) as u32;
+        Pixmap {
+            width,
+            height,
+            pixels: vec![[0.0; 4]; (width * height) as usize],
+        }
+    }
+
+    /// Blends the color over the pixel, `coverage` being the covered part of the pixel from 0.0 to 1.0.
+    /// A NaN coverage covers nothing.
+    fn blend_pixel(&mut self, x: i64, y: i64, color: Color, coverage: f32) {
+        if x < 0
+            || y < 0
+            || x >= self.width as i64
+            || y >= self.height as i64
+            || coverage.is_nan()
+            || coverage <= 0.0
+        {
+            return;
+        }
+        let alpha = color.a * coverage.min(1.0);
+        let pixel = &mut self.pixels[(y * self.width as i64 + x) as usize];
+        pixel[0] = color.r * alpha + pixel[0] * (1.0 - alpha);
+        pixel[1] = color.g * alpha + pixel[1] * (1.0 - alpha);
+        pixel[2] = color.b * alpha + pixel[2] * (1.0 - alpha);
+        pixel[3] = alpha + pixel[3] * (1.0 - alpha);
+    }
+
+    /// returns the pixel columns touched by the span, limited to the pixmap
+    fn clamp_columns(&self, start: f32, end: f32) -> Range<i64> {
+        start.floor().max(0.0) as i64..end.ceil().min(self.width as f32) as i64
+    }
+
+    /// returns the pixel rows touched by the span, limited to the pixmap
+    fn clamp_rows(&self, start: f32, end: f32) -> Range<i64> {
+        start.floor().max(0.0) as i64..end.ceil().min(self.height as f32) as i64
+    }
+
+    fn fill_rectangle(&mut self, top_left: Point, size: Size, color: Color) {
+        if !is_finite_point(top_left)
+            || !size.width.is_finite()
+            || !size.height.is_finite()
+            || size.width <= 0.0
+            || size.height <= 0.0
+        {
+            return;
+        }
+        let (left, right) = (top_left.x, top_left.x + size.width);
+        let (top, bottom) = (top_left.y, top_left.y + size.height);
+        for y in self.clamp_rows(top, bottom) {
+            let coverage_y = coverage_of_span(y as f32, top, bottom);
+            for x in self.clamp_columns(left, right) {
+                let coverage_x = coverage_of_span(x as f32, left, right);
+                self.blend_pixel(x, y, color, coverage_x * coverage_y);
+            }
+        }
+    }
+
+    /// strokes centered on the outline, the sides don't overlap so translucent colors stay even
+    fn stroke_rectangle(&mut self, top_left: Point, size: Size, color: Color, width: f32) {
+        let half_width = width / 2.0;
+        let outer_width = size.width + width;
+        self.fill_rectangle(
+            Point::new(top_left.x - half_width, top_left.y - half_width),
+            Size::new(outer_width, width),
+            color,
+        );
+        self.fill_rectangle(
+            Point::new(
+                top_left.x - half_width,
+                top_left.y + size.height - half_width,
+            ),
+            Size::new(outer_width, width),
+            color,
+        );
+        let inner_height = size.height - width;
+        if inner_height > 0.0 {
+            self.fill_rectangle(
+                Point::new(top_left.x - half_width, top_left.y + half_width),
+                Size::new(width, inner_height),
+                color,
+            );
+            self.fill_rectangle(
+                Point::new(
+                    top_left.x + size.width - half_width,
+                    top_left.y + half_width,
+                ),
+                Size::new(width, inner_height),
+                color,
+            );
+        }
+    }
+
+    /// strokes centered on the outline, as the area between the outline grown and shrunk by half the width
+    fn stroke_rounded_rectangle(
+        &mut self,
+        top_left: Point,
+        size: Size,
+        radii: CornerRadii,
+        color: Color,
+        width: f32,
+    ) {
+        let half_width = width / 2.0;
+        let grow = |radius: f32| {
+            if radius > 0.0 {
+                radius + half_width
+            } else {
+                0.0
+            }
+        };
+        let shrink = |radius: f32| (radius - half_width).max(0.0);
+        let outer = rounded_rectangle_points(
+            Point::new(top_left.x - half_width, top_left.y - half_width),
+            Size::new(size.width + width, size.height + width),
+            CornerRadii::new(
+                grow(radii.top_left),
+                grow(radii.top_right),
+                grow(radii.bottom_right),
+                grow(radii.bottom_left),
+            ),
+        );
+        if size.width <= width || size.height <= width {
+            self.fill_polygons(&[outer], color);
+            return;
+        }
+        let inner = rounded_rectangle_points(
+            Point::new(top_left.x + half_width, top_left.y + half_width),
+            Size::new(size.width - width, size.height - width),
+            CornerRadii::new(
+                shrink(radii.top_left),
+                shrink(radii.top_right),
+                shrink(radii.bottom_right),
+                shrink(radii.bottom_left),
+            ),
+        );
+        self.fill_polygons(&[outer, inner], color);
+    }
+
+    /// Fills the closed contours with the even odd rule, so inner contours become holes.
+    /// Contours with non finite points are left out.
+    fn fill_polygons(&mut self, contours: &[Vec<Point>], color: Color) {
+        let contours: Vec<&Vec<Point>> = contours
+            .iter()
+            .filter(|contour| contour.iter().all(|point| is_finite_point(*point)))
+            .collect();
+        let points = contours.iter().copied().flatten();
+        let (mut left, mut right) = (f32::MAX, f32::MIN);
+        let (mut top, mut bottom) = (f32::MAX, f32::MIN);
+        for point in points {
+            left = left.min(point.x);
+            right = right.max(point.x);
+            top = top.min(point.y);
+            bottom = bottom.max(point.y);
+        }
+        let columns = self.clamp_columns(left, right);
+        if columns.start >= columns.end {
+            return;
+        }
+        let mut row_coverage = vec![0.0; (columns.end - columns.start) as usize];
+        let mut crossings: Vec<f32> = vec![];
+        for y in self.clamp_rows(top, bottom) {
+            row_coverage.iter_mut().for_each(|coverage| *coverage = 0.0);
+            for sample in 0..POLYGON_SAMPLES {
+                let sample_y = y as f32 + (sample as f32 + 0.5) / POLYGON_SAMPLES as f32;
+                crossings.clear();
+                for contour in &contours {
+                    for (index, start) in contour.iter().enumerate() {
+                        let end = contour[(index + 1) % contour.len()];
+                        if (start.y <= sample_y) != (end.y <= sample_y) {
+                            let crossing = start.x
+                                + (sample_y - start.y) * (end.x - start.x) / (end.y - start.y);
+                            if crossing.is_finite() {
+                                crossings.push(crossing);
+                            }
+                        }
+                    }
+                }
+                crossings
+                    .sort_by(|first, second| first.partial_cmp(second).unwrap_or(Ordering::Equal));
+                for span in crossings.chunks(2) {
+                    if let [span_start, span_end] = span {
+                        for x in self.clamp_columns(*span_start, *span_end) {
+                            row_coverage[(x - columns.start) as usize] +=
+                                coverage_of_span(x as f32, *span_start, *span_end)
+                                    / POLYGON_SAMPLES as f32;
+                        }
+                    }
+                }
+            }
+            for (index, coverage) in row_coverage.iter().enumerate() {
+                self.blend_pixel(columns.start + index as i64, y, color, *coverage);
+            }
+        }
+    }
+
+    /// strokes a line with butt caps
+    fn stroke_line(&mut self, start: Point, end: Point, color: Color, width: f32) {
+        let direction_x = end.x - start.x;
+        let direction_y = end.y - start.y;
+        let length = (direction_x * direction_x + direction_y * direction_y).sqrt();
+        if length == 0.0 || !length.is_finite() || !width.is_finite() || width <= 0.0 {
+            return;
+        }
+        if direction_x == 0.0 || direction_y == 0.0 {
+            // axis aligned lines are rectangles, which get exact coverage
+            let half_width = width / 2.0;
+            let top_left = Point::new(
+                start.x.min(end.x) - if direction_x == 0.0 { half_width } else { 0.0 },
+                start.y.min(end.y) - if direction_y == 0.0 { half_width } else { 0.0 },
+            );
+            let size = if direction_x == 0.0 {
+                Size::new(width, length)
+            } else {
+                Size::new(length, width)
+            };
+            self.fill_rectangle(top_left, size, color);
+            return;
+        }
+        let (unit_x, unit_y) = (direction_x / length, direction_y / length);
+        let half_width = width / 2.0;
+        let left = start.x.min(end.x) - half_width;
+        let right = start.x.max(end.x) + half_width;
+        let top = start.y.min(end.y) - half_width;
+        let bottom = start.y.max(end.y) + half_width;
+        for y in self.clamp_rows(top, bottom) {
+            for x in self.clamp_columns(left, right) {
+                let relative_x = x as f32 + 0.5 - start.x;
+                let relative_y = y as f32 + 0.5 - start.y;
+                let along = relative_x * unit_x + relative_y * unit_y;
+                let across = (relative_x * unit_y - relative_y * unit_x).abs();
+                let coverage_across = (half_width + 0.5 - across).clamp(0.0, 1.0);
+                let coverage_along = (along.min(length - along) + 0.5).clamp(0.0, 1.0);
+                self.blend_pixel(x, y, color, coverage_across * coverage_along);
+            }
+        }
+    }
+
+    /// draws a single line of text, `position` being the top left corner
+    fn fill_text(&mut self, font: &Font, text: &str, position: Point, color: Color, size: f32) {
+        if !is_finite_point(position) || !size.is_finite() {
+            return;
+        }
+        let ascent = match font.horizontal_line_metrics(size) {
+            Some(line_metrics) => line_metrics.ascent,
+            None => size,
+        };
+        let baseline = position.y + ascent;
+        let mut pen_x = position.x;
+        for character in text.chars() {
+            let (metrics, bitmap) = font.rasterize(character, size);
+            let glyph_left = (pen_x + metrics.xmin as f32).round() as i64;
+            let glyph_top = (baseline - metrics.ymin as f32 - metrics.height as f32).round() as i64;
+            for row in 0..metrics.height {
+                for column in 0..metrics.width {
+                    let coverage = bitmap[row * metrics.width + column] as f32 / 255.0;
+                    self.blend_pixel(
+                        glyph_left + column as i64,
+                        glyph_top + row as i64,
+                        color,
+                        coverage,
+                    );
+                }
+            }
+            pen_x += metrics.advance_width;
+        }
+    }
+
+    fn into_rgba8(self) -> Vec<u8> {
+        let mut rgba = Vec::with_capacity(self.pixels.len() * 4);
+        for [r, g, b, a] in self.pixels {
+            if a <= 0.0 {
+                rgba.extend_from_slice(&[0, 0, 0, 0]);
+            } else {
+                rgba.push(to_u8(r / a));
+                rgba.push(to_u8(g / a));
+                rgba.push(to_u8(b / a));
+                rgba.push(to_u8(a));
+            }
+        }
+        rgba
+    }
+}
+
+fn is_finite_point(point: Point) -> bool {
+    point.x.is_finite() && point.y.is_finite()
+}
+
+/// returns how much of the pixel starting at `pixel_start` lies between `start` and `end`
+fn coverage_of_span(pixel_start: f32, start: f32, end: f32) -> f32 {
+    ((pixel_start + 1.0).min(end) - pixel_start.max(start)).clamp(0.0, 1.0)
+}
+
+fn to_u8(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
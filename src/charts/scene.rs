@@ -0,0 +1,321 @@
+use crate::charts::error::ChartsLibError;
+use crate::charts::fill::Fill;
+use crate::charts::pdf_renderer::PdfRenderer;
+use crate::charts::png_renderer::PngRenderer;
+use crate::charts::svg_renderer;
+use iced::{Color, Point, Size};
+use std::f32::consts::FRAC_PI_2;
+use std::path::Path;
+
+/// amount of line segments a rounded corner is approximated with
+const CORNER_SEGMENTS: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CornerRadii {
+    pub top_left: f32,
+    pub top_right: f32,
+    pub bottom_right: f32,
+    pub bottom_left: f32,
+}
+
+impl CornerRadii {
+    pub fn new(top_left: f32, top_right: f32, bottom_right: f32, bottom_left: f32) -> Self {
+        CornerRadii {
+            top_left,
+            top_right,
+            bottom_right,
+            bottom_left,
+        }
+    }
+
+    pub fn zero() -> Self {
+        CornerRadii::new(0.0, 0.0, 0.0, 0.0)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.top_left <= 0.0
+            && self.top_right <= 0.0
+            && self.bottom_right <= 0.0
+            && self.bottom_left <= 0.0
+    }
+
+    /// returns the radii scaled down so that neighbouring corners don't overlap within `size`
+    pub fn fitted(&self, size: Size) -> Self {
+        let fit = |length: f32, first: f32, second: f32| {
+            if first + second > length {
+                length.max(0.0) / (first + second)
+            } else {
+                1.0
+            }
+        };
+        let scale = fit(size.width, self.top_left, self.top_right)
+            .min(fit(size.width, self.bottom_left, self.bottom_right))
+            .min(fit(size.height, self.top_left, self.bottom_left))
+            .min(fit(size.height, self.top_right, self.bottom_right));
+        CornerRadii::new(
+            self.top_left.max(0.0) * scale,
+            self.top_right.max(0.0) * scale,
+            self.bottom_right.max(0.0) * scale,
+            self.bottom_left.max(0.0) * scale,
+        )
+    }
+}
+
+/// Returns the outline of the rounded rectangle as a convex polygon, clockwise starting at the top left corner.
+/// Renderers without curves draw this polygon.
+pub fn rounded_rectangle_points(top_left: Point, size: Size, radii: CornerRadii) -> Vec<Point> {
+    let radii = radii.fitted(size);
+    let left = top_left.x;
+    let right = top_left.x + size.width;
+    let top = top_left.y;
+    let bottom = top_left.y + size.height;
+    // (corner center, radius, start angle), angles growing clockwise since y points down
+    let corners = [
+        (
+            Point::new(left + radii.top_left, top + radii.top_left),
+            radii.top_left,
+            2.0 * FRAC_PI_2,
+        ),
+        (
+            Point::new(right - radii.top_right, top + radii.top_right),
+            radii.top_right,
+            3.0 * FRAC_PI_2,
+        ),
+        (
+            Point::new(right - radii.bottom_right, bottom - radii.bottom_right),
+            radii.bottom_right,
+            0.0,
+        ),
+        (
+            Point::new(left + radii.bottom_left, bottom - radii.bottom_left),
+            radii.bottom_left,
+            FRAC_PI_2,
+        ),
+    ];
+    let mut points = vec![];
+    for (center, radius, start_angle) in corners.iter() {
+        if *radius <= 0.0 {
+            points.push(*center);
+            continue;
+        }
+        for segment in 0..=CORNER_SEGMENTS {
+            let angle = start_angle + FRAC_PI_2 * segment as f32 / CORNER_SEGMENTS as f32;
+            points.push(Point::new(
+                center.x + radius * angle.cos(),
+                center.y + radius * angle.sin(),
+            ));
+        }
+    }
+    points
+}
+
+/// A renderer independent description of a single drawing operation.
+/// Positions are in pixels relative to the top left corner of the scene.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SceneElement {
+    Rectangle {
+        top_left: Point,
+        size: Size,
+        color: Color,
+    },
+    /// Renderers without gradients or patterns draw `Fill::decompose` instead.
+    FilledRectangle {
+        top_left: Point,
+        size: Size,
+        fill: Fill,
+    },
+    /// `radii` are already fitted to `size`
+    RoundedRectangle {
+        top_left: Point,
+        size: Size,
+        radii: CornerRadii,
+        fill: Fill,
+    },
+    RoundedRectangleOutline {
+        top_left: Point,
+        size: Size,
+        radii: CornerRadii,
+        color: Color,
+        width: f32,
+    },
+    RectangleOutline {
+        top_left: Point,
+        size: Size,
+        color: Color,
+        width: f32,
+    },
+    Line {
+        start: Point,
+        end: Point,
+        color: Color,
+        width: f32,
+    },
+    /// `position` is the top left corner of the text.
+    Text {
+        content: String,
+        position: Point,
+        color: Color,
+        size: f32,
+    },
+}
+
+/// The result of the layout stage: everything needed to draw a chart of the given size,
+/// in drawing order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scene {
+    pub size: Size,
+    pub elements: Vec<SceneElement>,
+}
+
+impl Scene {
+    pub fn new(size: Size) -> Self {
+        Scene {
+            size,
+            elements: vec![],
+        }
+    }
+
+    pub fn width(&self) -> f32 {
+        self.size.width
+    }
+
+    pub fn height(&self) -> f32 {
+        self.size.height
+    }
+
+    pub fn fill_rectangle(&mut self, top_left: Point, size: Size, color: Color) {
+        self.elements.push(SceneElement::Rectangle {
+            top_left,
+            size,
+            color,
+        });
+    }
+
+    /// solid fills are added as a plain rectangle
+    pub fn fill_rectangle_with(&mut self, top_left: Point, size: Size, fill: &Fill) {
+        match fill {
+            Fill::Solid(color) => self.fill_rectangle(top_left, size, *color),
+            _ => self.elements.push(SceneElement::FilledRectangle {
+                top_left,
+                size,
+                fill: fill.clone(),
+            }),
+        }
+    }
+
+    pub fn stroke_rectangle(&mut self, top_left: Point, size: Size, color: Color, width: f32) {
+        self.elements.push(SceneElement::RectangleOutline {
+            top_left,
+            size,
+            color,
+            width,
+        });
+    }
+
+    /// falls back to a plain rectangle if no corner is rounded
+    pub fn fill_rounded_rectangle(
+        &mut self,
+        top_left: Point,
+        size: Size,
+        radii: CornerRadii,
+        fill: &Fill,
+    ) {
+        let radii = radii.fitted(size);
+        if radii.is_zero() {
+            self.fill_rectangle_with(top_left, size, fill);
+        } else {
+            self.elements.push(SceneElement::RoundedRectangle {
+                top_left,
+                size,
+                radii,
+                fill: fill.clone(),
+            });
+        }
+    }
+
+    /// falls back to a plain rectangle outline if no corner is rounded
+    pub fn stroke_rounded_rectangle(
+        &mut self,
+        top_left: Point,
+        size: Size,
+        radii: CornerRadii,
+        color: Color,
+        width: f32,
+    ) {
+        let radii = radii.fitted(size);
+        if radii.is_zero() {
+            self.stroke_rectangle(top_left, size, color, width);
+        } else {
+            self.elements.push(SceneElement::RoundedRectangleOutline {
+                top_left,
+                size,
+                radii,
+                color,
+                width,
+            });
+        }
+    }
+
+    pub fn stroke_line(&mut self, start: Point, end: Point, color: Color, width: f32) {
+        self.elements.push(SceneElement::Line {
+            start,
+            end,
+            color,
+            width,
+        });
+    }
+
+    pub fn fill_text(&mut self, content: String, position: Point, color: Color, size: f32) {
+        self.elements.push(SceneElement::Text {
+            content,
+            position,
+            color,
+            size,
+        });
+    }
+
+    /// appends the elements of `other`, drawing them on top of the existing ones
+    pub fn extend(&mut self, other: &Scene) {
+        self.elements.extend(other.elements.iter().cloned());
+    }
+}
+
+/// Implemented by every chart that can be laid out without a window, which makes it exportable.
+pub trait SceneChart {
+    /// returns the chart laid out for the given size, without hover or selection highlights
+    fn create_scene(&self, size: Size) -> Scene;
+
+    fn to_svg(&self, size: Size) -> String {
+        svg_renderer::render_svg(&self.create_scene(size))
+    }
+
+    fn save_svg<P: AsRef<Path>>(&self, size: Size, path: P) -> Result<(), ChartsLibError> {
+        svg_renderer::save_svg(&self.create_scene(size), path)
+    }
+
+    fn to_png(&self, size: Size, renderer: &PngRenderer) -> Result<Vec<u8>, ChartsLibError> {
+        renderer.render_png(&self.create_scene(size))
+    }
+
+    fn save_png<P: AsRef<Path>>(
+        &self,
+        size: Size,
+        renderer: &PngRenderer,
+        path: P,
+    ) -> Result<(), ChartsLibError> {
+        renderer.save_png(&self.create_scene(size), path)
+    }
+
+    fn to_pdf(&self, size: Size, renderer: &PdfRenderer) -> Result<Vec<u8>, ChartsLibError> {
+        renderer.render_pdf(&self.create_scene(size))
+    }
+
+    fn save_pdf<P: AsRef<Path>>(
+        &self,
+        size: Size,
+        renderer: &PdfRenderer,
+        path: P,
+    ) -> Result<(), ChartsLibError> {
+        renderer.save_pdf(&self.create_scene(size), path)
+    }
+}
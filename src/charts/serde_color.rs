@@ -0,0 +1,83 @@
+//! Serde helpers for iced colors, which are written as `[r, g, b, a]` with components from 0.0 to 1.0.
+//! Use them with `#[serde(with = "...")]`.
+
+use iced::Color;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+
+fn to_array(color: &Color) -> [f32; 4] {
+    [color.r, color.g, color.b, color.a]
+}
+
+fn from_array(array: [f32; 4]) -> Color {
+    Color::from_rgba(array[0], array[1], array[2], array[3])
+}
+
+pub mod color {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+        to_array(color).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        Ok(from_array(<[f32; 4]>::deserialize(deserializer)?))
+    }
+}
+
+pub mod option_color {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        color: &Option<Color>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        color.as_ref().map(to_array).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Color>, D::Error> {
+        Ok(Option::<[f32; 4]>::deserialize(deserializer)?.map(from_array))
+    }
+}
+
+pub mod color_map {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        color_map: &HashMap<u32, Color>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut entries: Vec<(u32, [f32; 4])> = color_map
+            .iter()
+            .map(|(dataset_id, color)| (*dataset_id, to_array(color)))
+            .collect();
+        entries.sort_unstable_by_key(|(dataset_id, _)| *dataset_id);
+        serializer.collect_map(entries)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<u32, Color>, D::Error> {
+        Ok(HashMap::<u32, [f32; 4]>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(dataset_id, color)| (dataset_id, from_array(color)))
+            .collect())
+    }
+}
+
+pub mod color_vec {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(colors: &[Color], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(colors.iter().map(to_array))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Color>, D::Error> {
+        Ok(Vec::<[f32; 4]>::deserialize(deserializer)?
+            .into_iter()
+            .map(from_array)
+            .collect())
+    }
+}
@@ -0,0 +1,292 @@
+use crate::charts::error::{ChartsLibError, ErrorKind};
+use crate::charts::fill::{
+    Fill, GradientDirection, HatchPattern, DOT_SIZE, HATCH_LINE_WIDTH, HATCH_SPACING,
+};
+use crate::charts::scene::{CornerRadii, Scene, SceneElement};
+use iced::{Color, Point, Size};
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+const FONT_FAMILY: &str = "sans-serif";
+
+/// returns the scene as a standalone SVG document
+pub fn render_svg(scene: &Scene) -> String {
+    let mut svg = String::new();
+    // writing into a String can't fail
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        w = scene.width(),
+        h = scene.height()
+    );
+    for (index, element) in scene.elements.iter().enumerate() {
+        let _ = match element {
+            SceneElement::Rectangle {
+                top_left,
+                size,
+                color,
+            } => writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" {}/>"#,
+                top_left.x,
+                top_left.y,
+                size.width,
+                size.height,
+                paint("fill", *color)
+            ),
+            SceneElement::FilledRectangle {
+                top_left,
+                size,
+                fill,
+            } => {
+                let fill_paint = write_fill_definition(&mut svg, &format!("fill{}", index), fill);
+                writeln!(
+                    svg,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" {}/>"#,
+                    top_left.x, top_left.y, size.width, size.height, fill_paint
+                )
+            }
+            SceneElement::RoundedRectangle {
+                top_left,
+                size,
+                radii,
+                fill,
+            } => {
+                let fill_paint = write_fill_definition(&mut svg, &format!("fill{}", index), fill);
+                writeln!(
+                    svg,
+                    r#"<path d="{}" {}/>"#,
+                    rounded_rectangle_path(*top_left, *size, *radii),
+                    fill_paint
+                )
+            }
+            SceneElement::RoundedRectangleOutline {
+                top_left,
+                size,
+                radii,
+                color,
+                width,
+            } => writeln!(
+                svg,
+                r#"<path d="{}" fill="none" {} stroke-width="{}" stroke-linejoin="miter"/>"#,
+                rounded_rectangle_path(*top_left, *size, *radii),
+                paint("stroke", *color),
+                width
+            ),
+            SceneElement::RectangleOutline {
+                top_left,
+                size,
+                color,
+                width,
+            } => writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="none" {} stroke-width="{}" stroke-linejoin="miter"/>"#,
+                top_left.x,
+                top_left.y,
+                size.width,
+                size.height,
+                paint("stroke", *color),
+                width
+            ),
+            SceneElement::Line {
+                start,
+                end,
+                color,
+                width,
+            } => writeln!(
+                svg,
+                r#"<line x1="{}" y1="{}" x2="{}" y2="{}" {} stroke-width="{}" stroke-linecap="butt"/>"#,
+                start.x,
+                start.y,
+                end.x,
+                end.y,
+                paint("stroke", *color),
+                width
+            ),
+            SceneElement::Text {
+                content,
+                position,
+                color,
+                size,
+            } => writeln!(
+                svg,
+                r#"<text x="{}" y="{}" font-family="{}" font-size="{}" dominant-baseline="text-before-edge" {}>{}</text>"#,
+                position.x,
+                position.y,
+                FONT_FAMILY,
+                size,
+                paint("fill", *color),
+                escape_xml(content)
+            ),
+        };
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+pub fn save_svg<P: AsRef<Path>>(scene: &Scene, path: P) -> Result<(), ChartsLibError> {
+    fs::write(path.as_ref(), render_svg(scene)).map_err(|error| {
+        ChartsLibError::new(
+            ErrorKind::ExportFailed,
+            format!(
+                "Could not write the SVG to {}: {}",
+                path.as_ref().display(),
+                error
+            ),
+        )
+    })
+}
+
+/// returns the path data of the rectangle, corners with a radius of 0 stay square
+fn rounded_rectangle_path(top_left: Point, size: Size, radii: CornerRadii) -> String {
+    let left = top_left.x;
+    let right = top_left.x + size.width;
+    let top = top_left.y;
+    let bottom = top_left.y + size.height;
+    format!(
+        "M{} {} H{} A{r1} {r1} 0 0 1 {} {} V{} A{r2} {r2} 0 0 1 {} {} H{} A{r3} {r3} 0 0 1 {} {} V{} A{r0} {r0} 0 0 1 {} {} Z",
+        left + radii.top_left,
+        top,
+        right - radii.top_right,
+        right,
+        top + radii.top_right,
+        bottom - radii.bottom_right,
+        right - radii.bottom_right,
+        bottom,
+        left + radii.bottom_left,
+        left,
+        bottom - radii.bottom_left,
+        top + radii.top_left,
+        left + radii.top_left,
+        top,
+        r0 = radii.top_left,
+        r1 = radii.top_right,
+        r2 = radii.bottom_right,
+        r3 = radii.bottom_left
+    )
+}
+
+/// Adds the gradient or pattern of the fill, named `fill_id`, and returns the fill attribute referencing it.
+/// Patterns use scene coordinates, so they line up across neighbouring rectangles like in the other renderers.
+fn write_fill_definition(svg: &mut String, fill_id: &str, fill: &Fill) -> String {
+    match fill {
+        Fill::Solid(color) => return paint("fill", *color),
+        Fill::LinearGradient {
+            start_color,
+            end_color,
+            direction,
+        } => {
+            let (x2, y2) = match direction {
+                GradientDirection::Horizontal => (1, 0),
+                GradientDirection::Vertical => (0, 1),
+            };
+            let _ = writeln!(
+                svg,
+                r#"<defs><linearGradient id="{}" x1="0" y1="0" x2="{}" y2="{}">{}{}</linearGradient></defs>"#,
+                fill_id,
+                x2,
+                y2,
+                gradient_stop(0.0, *start_color),
+                gradient_stop(1.0, *end_color)
+            );
+        }
+        Fill::Hatch {
+            pattern,
+            color,
+            background_color,
+        } => {
+            let mut tile = String::new();
+            if let Some(background_color) = background_color {
+                let _ = write!(
+                    tile,
+                    r#"<rect width="{s}" height="{s}" {}/>"#,
+                    paint("fill", *background_color),
+                    s = HATCH_SPACING
+                );
+            }
+            let s = HATCH_SPACING;
+            // every line is continued into the neighbouring tiles at the corners
+            let rising = [
+                (0.0, s, s, 0.0),
+                (-1.0, 1.0, 1.0, -1.0),
+                (s - 1.0, s + 1.0, s + 1.0, s - 1.0),
+            ];
+            let falling = [
+                (0.0, 0.0, s, s),
+                (-1.0, s - 1.0, 1.0, s + 1.0),
+                (s - 1.0, -1.0, s + 1.0, 1.0),
+            ];
+            let lines: Vec<&(f32, f32, f32, f32)> = match pattern {
+                HatchPattern::Diagonal => rising.iter().collect(),
+                HatchPattern::CrossHatch => rising.iter().chain(falling.iter()).collect(),
+                HatchPattern::Dots => vec![],
+            };
+            for (x1, y1, x2, y2) in lines {
+                let _ = write!(
+                    tile,
+                    r#"<line x1="{}" y1="{}" x2="{}" y2="{}" {} stroke-width="{}"/>"#,
+                    x1,
+                    y1,
+                    x2,
+                    y2,
+                    paint("stroke", *color),
+                    HATCH_LINE_WIDTH
+                );
+            }
+            if let HatchPattern::Dots = pattern {
+                let _ = write!(
+                    tile,
+                    r#"<rect x="{p}" y="{p}" width="{d}" height="{d}" {}/>"#,
+                    paint("fill", *color),
+                    p = (s - DOT_SIZE) / 2.0,
+                    d = DOT_SIZE
+                );
+            }
+            let _ = writeln!(
+                svg,
+                r#"<defs><pattern id="{}" patternUnits="userSpaceOnUse" width="{s}" height="{s}">{}</pattern></defs>"#,
+                fill_id,
+                tile,
+                s = s
+            );
+        }
+    }
+    format!(r#"fill="url(#{})""#, fill_id)
+}
+
+fn gradient_stop(offset: f32, color: Color) -> String {
+    let [r, g, b, _] = color.into_rgba8();
+    format!(
+        r#"<stop offset="{}" stop-color="rgb({},{},{})" stop-opacity="{}"/>"#,
+        offset, r, g, b, color.a
+    )
+}
+
+/// returns the color as svg attributes, e.g. `fill="rgb(0,0,0)" fill-opacity="0.5"`
+fn paint(attribute: &str, color: Color) -> String {
+    let [r, g, b, a] = color.into_rgba8();
+    if a == 255 {
+        format!(r#"{}="rgb({},{},{})""#, attribute, r, g, b)
+    } else {
+        format!(
+            r#"{}="rgb({},{},{})" {}-opacity="{}""#,
+            attribute, r, g, b, attribute, color.a
+        )
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
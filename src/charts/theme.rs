@@ -0,0 +1,134 @@
+use crate::charts::bar_chart_iced_struct::BarChartIcedStruct;
+use crate::charts::bar_chart_style_info_iced::BarChartStyleInfoIced;
+use crate::charts::chart_value::ChartValue;
+use crate::charts::error::{ChartsLibError, ErrorKind};
+use crate::charts::palette::QualitativePalette;
+use crate::charts::serde_color;
+use iced::Color;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+const LIGHT_GRID_COLOR: Color = Color::from_rgb(175.0 / 255.0, 181.0 / 255.0, 189.0 / 255.0);
+const DARK_BACKGROUND_COLOR: Color = Color::from_rgb(0.12, 0.12, 0.13);
+const DARK_TEXT_COLOR: Color = Color::from_rgb(0.9, 0.9, 0.9);
+const DARK_AXIS_COLOR: Color = Color::from_rgb(0.8, 0.8, 0.8);
+const DARK_GRID_COLOR: Color = Color::from_rgb(0.32, 0.33, 0.35);
+/// alpha of the text color used for the hover highlight
+const HOVER_ALPHA: f32 = 0.08;
+
+/// The colors of a chart in one place, so every chart can be restyled at once.
+/// Fields missing in a theme file are taken from the light theme.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    pub name: String,
+    #[serde(with = "serde_color::color")]
+    pub background_color: Color,
+    /// headline, cluster names, markings and legend
+    #[serde(with = "serde_color::color")]
+    pub text_color: Color,
+    #[serde(with = "serde_color::color")]
+    pub axis_color: Color,
+    /// separator lines
+    #[serde(with = "serde_color::color")]
+    pub grid_color: Color,
+    /// outline of selected bars and the crosshair
+    #[serde(with = "serde_color::color")]
+    pub highlight_color: Color,
+    /// dataset colors, shaded lighter and darker if there are more datasets
+    #[serde(with = "serde_color::color_vec")]
+    pub palette: Vec<Color>,
+}
+
+impl Theme {
+    pub fn light() -> Self {
+        Theme {
+            name: String::from("light"),
+            background_color: Color::WHITE,
+            text_color: Color::BLACK,
+            axis_color: Color::BLACK,
+            grid_color: LIGHT_GRID_COLOR,
+            highlight_color: Color::BLACK,
+            palette: QualitativePalette::default().colors(),
+        }
+    }
+
+    pub fn dark() -> Self {
+        Theme {
+            name: String::from("dark"),
+            background_color: DARK_BACKGROUND_COLOR,
+            text_color: DARK_TEXT_COLOR,
+            axis_color: DARK_AXIS_COLOR,
+            grid_color: DARK_GRID_COLOR,
+            highlight_color: Color::WHITE,
+            palette: QualitativePalette::default().colors(),
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, ChartsLibError> {
+        serde_json::from_str(json).map_err(|error| invalid_theme_error(error.to_string()))
+    }
+
+    pub fn from_toml(toml: &str) -> Result<Self, ChartsLibError> {
+        toml::from_str(toml).map_err(|error| invalid_theme_error(error.to_string()))
+    }
+
+    /// loads a `.toml` or `.json` theme file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ChartsLibError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|error| {
+            invalid_theme_error(format!("Could not read {}: {}", path.display(), error))
+        })?;
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => Theme::from_toml(&content),
+            Some("json") => Theme::from_json(&content),
+            _ => Err(invalid_theme_error(format!(
+                "{} is neither a .toml nor a .json file",
+                path.display()
+            ))),
+        }
+    }
+
+    /// overwrites all colors of the style covered by the theme
+    pub fn apply(&self, style: &mut BarChartStyleInfoIced) {
+        style.background_color = Some(self.background_color);
+        style.headline_color = Some(self.text_color);
+        style.cluster_color = Some(self.text_color);
+        style.markings_color = Some(self.text_color);
+        style.legend_text_color = Some(self.text_color);
+        style.axis_stroke_color = Some(self.axis_color);
+        style.separator_stroke_color = Some(self.grid_color);
+        style.selection_stroke_color = Some(self.highlight_color);
+        style.crosshair_color = Some(self.highlight_color);
+        style.hover_color = Some(Color {
+            a: HOVER_ALPHA,
+            ..self.text_color
+        });
+        style.standard_color_map = self.palette.clone();
+    }
+
+    /// applies the theme to all charts at once, e.g. every chart of an application
+    pub fn apply_to_charts<'a, T, I>(&self, charts: I)
+    where
+        T: ChartValue,
+        I: IntoIterator<Item = &'a mut BarChartIcedStruct<T>>,
+    {
+        for chart in charts {
+            chart.set_theme(self);
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::light()
+    }
+}
+
+fn invalid_theme_error(description: String) -> ChartsLibError {
+    ChartsLibError::new(
+        ErrorKind::InvalidTheme,
+        format!("Could not load the theme: {}", description),
+    )
+}
@@ -1,6 +1,5 @@
 use iced::canvas::Cursor;
-use iced::widget::canvas::Text;
-use iced::{Color, Point, Rectangle};
+use iced::{Color, Point, Rectangle, Size};
 
 pub fn obsolete_cursor_is_over_inside_canvas(
     cursor: &Cursor,
@@ -19,18 +18,34 @@ pub fn obsolete_cursor_is_over_inside_canvas(
     }
 }
 
-pub fn placeholder_get_text_width(text: &Text) -> f32 {
+/// returns the rectangle spanned by two arbitrary corner points
+pub fn rectangle_from_points(first: Point, second: Point) -> Rectangle {
+    let top_left = Point::new(first.x.min(second.x), first.y.min(second.y));
+    Rectangle::new(
+        top_left,
+        Size::new((first.x - second.x).abs(), (first.y - second.y).abs()),
+    )
+}
+
+pub fn rectangles_overlap(first: &Rectangle, second: &Rectangle) -> bool {
+    first.x < second.x + second.width
+        && second.x < first.x + first.width
+        && first.y < second.y + second.height
+        && second.y < first.y + first.height
+}
+
+pub fn placeholder_get_text_width(text: &str) -> f32 {
     30.0
 }
 
-pub fn placeholder_get_text_height(text: &Text) -> f32 {
+pub fn placeholder_get_text_height(text: &str) -> f32 {
     20.0
 }
 
 pub fn placeholder_get_max_text_width(strings: &[String]) -> f32 {
     let mut max_text_width = 0.0;
     for text in strings {
-        let text_width = placeholder_get_text_width(&Text::from(&*text.as_str()));
+        let text_width = placeholder_get_text_width(text);
         if text_width > max_text_width {
             max_text_width = text_width;
         }
@@ -41,7 +56,7 @@ pub fn placeholder_get_max_text_width(strings: &[String]) -> f32 {
 pub fn placeholder_get_max_text_height(strings: &[String]) -> f32 {
     let mut max_text_height = 0.0;
     for text in strings {
-        let text_height = placeholder_get_text_height(&Text::from(&*text.as_str()));
+        let text_height = placeholder_get_text_height(text);
         if text_height > max_text_height {
             max_text_height = text_height;
         }
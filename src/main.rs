@@ -2,8 +2,6 @@
 #![allow(unused_variables)]
 #![allow(unused_imports)]
 
-mod charts;
-
 use rand::distributions::Alphanumeric;
 use rand::{thread_rng, Rng};
 
@@ -16,19 +14,23 @@ use iced::{
     VerticalAlignment,
 };
 
-use crate::charts::*;
+use iced_charts_lib::charts::*;
 
-use crate::charts::axis_enums::{
+use iced::widget::pane_grid::Axis;
+use iced_charts_lib::charts::axis_enums::{
     AxisOrientation, BarChartDataAxis, XAxisOrientation, YAxisOrientation,
 };
-use crate::charts::bar_chart_data::{BarChartData, Dataset};
-use crate::charts::bar_chart_iced_struct::BarChartIcedStruct;
-use crate::charts::coordinate_system::{CoordinateSystem, CoordinateSystemConfig};
-use charts::bar_chart_style_info_iced::BarChartStyleInfoIced;
-use iced::widget::pane_grid::Axis;
+use iced_charts_lib::charts::bar_chart_data::{BarChartData, Dataset};
+use iced_charts_lib::charts::bar_chart_iced_struct::{
+    BarChartIcedStruct, BarChartMessage, ANIMATION_FRAME_INTERVAL,
+};
+use iced_charts_lib::charts::bar_chart_style_info_iced::BarChartStyleInfoIced;
+use iced_charts_lib::charts::coordinate_system::{CoordinateSystem, CoordinateSystemConfig};
+use iced_charts_lib::charts::theme::Theme;
 use std::borrow::Borrow;
 use std::collections::HashMap;
 use std::fmt::Pointer;
+use std::time::{Duration, Instant};
 
 /*
 Vec<Rectangle, f32> liste mit balken zum durchsuchen für hover, hover und blase per overlay um erneutes zeichnen zu verhindern?
@@ -61,16 +63,21 @@ struct ChartDrawingTest {
     x_neg_y_complete_y: ChartDrawer1,
     add_button: button::State,
     remove_button: button::State,
+    theme_button: button::State,
+    dark_theme: bool,
 }
 
 struct ChartDrawer1 {
     chart: BarChartIcedStruct,
 }
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub enum Message {
     NewData,
     AddButton,
     RemoveButton,
+    ThemeButton,
+    AnimationTick(Instant),
+    Chart(usize, BarChartMessage),
 }
 
 impl ChartDrawer1 {
@@ -83,11 +90,11 @@ impl ChartDrawer1 {
         let dataset2 = Dataset::new(1, String::from("test1"), vec![-1.2, -2.3, -2.4]);
         let dataset3 = Dataset::new(2, String::from("test2"), vec![-1.5, -1.4, -1.3]);
         let dataset4 = Dataset::new(3, String::from("test3"), vec![1.1, 3.0, 2.3]);
-        let dataset5 = Dataset::new(4, String::from("test4"), vec![-0.3, -2.1]);
+        let dataset5 = Dataset::new(4, String::from("test4"), vec![-0.3, -2.1, -1.1]);
         let dataset6 = Dataset::new(5, String::from("test5"), vec![1.5, 2.5, 2.0]);
-        let dataset7 = Dataset::new(3, String::from("test3"), vec![-3.0, -2.7, -1.8]);
-        let dataset8 = Dataset::new(4, String::from("test4"), vec![2.4, 1.4]);
-        let dataset9 = Dataset::new(5, String::from("test5"), vec![1.5, 2.2, 3.0]);
+        let dataset7 = Dataset::new(6, String::from("test6"), vec![-3.0, -2.7, -1.8]);
+        let dataset8 = Dataset::new(7, String::from("test7"), vec![2.4, 1.4, 0.8]);
+        let dataset9 = Dataset::new(8, String::from("test8"), vec![1.5, 2.2, 3.0]);
 
         let mut hashmap_object: HashMap<u32, Color> = HashMap::new();
         hashmap_object.insert(0, Color::from_rgb(252.0 / 255.0, 7.0 / 255.0, 3.0 / 255.0));
@@ -106,6 +113,10 @@ impl ChartDrawer1 {
             Color::from_rgb(3.0 / 255.0, 252.0 / 255.0, 132.0 / 255.0),
         );
 
+        let mut style = BarChartStyleInfoIced::new();
+        style.show_crosshair = true;
+        style.animation_duration_ms = Some(400);
+
         let data_second_object = BarChartData::new(vec![
             dataset1, dataset2, dataset3, dataset4, dataset5, dataset6, dataset7, dataset8,
             dataset9,
@@ -121,7 +132,7 @@ impl ChartDrawer1 {
                 String::from("test2"),
                 String::from("test3"),
             ],
-            BarChartStyleInfoIced::new(),
+            style,
         )
         .unwrap();
 
@@ -131,6 +142,42 @@ impl ChartDrawer1 {
     }
 }
 
+impl ChartDrawingTest {
+    fn charts(&self) -> [&BarChartIcedStruct; 12] {
+        [
+            &self.x_pos_y_pos_x.chart,
+            &self.x_pos_y_neg_x.chart,
+            &self.x_neg_y_pos_x.chart,
+            &self.x_neg_y_neg_x.chart,
+            &self.x_pos_y_pos_y.chart,
+            &self.x_neg_y_pos_y.chart,
+            &self.x_pos_y_neg_y.chart,
+            &self.x_neg_y_neg_y.chart,
+            &self.x_complete_y_neg_y.chart,
+            &self.x_complete_y_pos_y.chart,
+            &self.x_pos_y_complete_y.chart,
+            &self.x_neg_y_complete_y.chart,
+        ]
+    }
+
+    fn charts_mut(&mut self) -> [&mut BarChartIcedStruct; 12] {
+        [
+            &mut self.x_pos_y_pos_x.chart,
+            &mut self.x_pos_y_neg_x.chart,
+            &mut self.x_neg_y_pos_x.chart,
+            &mut self.x_neg_y_neg_x.chart,
+            &mut self.x_pos_y_pos_y.chart,
+            &mut self.x_neg_y_pos_y.chart,
+            &mut self.x_pos_y_neg_y.chart,
+            &mut self.x_neg_y_neg_y.chart,
+            &mut self.x_complete_y_neg_y.chart,
+            &mut self.x_complete_y_pos_y.chart,
+            &mut self.x_pos_y_complete_y.chart,
+            &mut self.x_neg_y_complete_y.chart,
+        ]
+    }
+}
+
 impl Application for ChartDrawingTest {
     type Executor = executor::Default;
     type Message = Message;
@@ -201,6 +248,8 @@ impl Application for ChartDrawingTest {
                 ),
                 add_button: button::State::new(),
                 remove_button: button::State::new(),
+                theme_button: button::State::new(),
+                dark_theme: false,
             },
             Command::none(),
         )
@@ -236,85 +285,154 @@ impl Application for ChartDrawingTest {
                     ],
                 );
 
-                self.x_pos_y_pos_x.chart.add_dataset(dataset.clone());
-                self.x_pos_y_neg_x.chart.add_dataset(dataset.clone());
-                self.x_neg_y_pos_x.chart.add_dataset(dataset.clone());
-                self.x_neg_y_neg_x.chart.add_dataset(dataset.clone());
-                self.x_pos_y_pos_y.chart.add_dataset(dataset.clone());
-                self.x_neg_y_pos_y.chart.add_dataset(dataset.clone());
-                self.x_pos_y_neg_y.chart.add_dataset(dataset.clone());
-                self.x_neg_y_neg_y.chart.add_dataset(dataset.clone());
-                self.x_complete_y_neg_y.chart.add_dataset(dataset.clone());
-                self.x_complete_y_pos_y.chart.add_dataset(dataset.clone());
-                self.x_pos_y_complete_y.chart.add_dataset(dataset.clone());
-                self.x_neg_y_complete_y.chart.add_dataset(dataset);
+                for chart in self.charts_mut().iter_mut() {
+                    if let Err(error) = chart.add_dataset(dataset.clone()) {
+                        println!("{}", error);
+                    }
+                }
                 println!("Add Button pressed");
             }
             Message::RemoveButton => {
-                self.x_pos_y_pos_x.chart.remove_last_dataset();
-                self.x_pos_y_neg_x.chart.remove_last_dataset();
-                self.x_neg_y_pos_x.chart.remove_last_dataset();
-                self.x_neg_y_neg_x.chart.remove_last_dataset();
-                self.x_pos_y_pos_y.chart.remove_last_dataset();
-                self.x_neg_y_pos_y.chart.remove_last_dataset();
-                self.x_pos_y_neg_y.chart.remove_last_dataset();
-                self.x_neg_y_neg_y.chart.remove_last_dataset();
-                self.x_complete_y_neg_y.chart.remove_last_dataset();
-                self.x_complete_y_pos_y.chart.remove_last_dataset();
-                self.x_pos_y_complete_y.chart.remove_last_dataset();
-                self.x_neg_y_complete_y.chart.remove_last_dataset();
+                for chart in self.charts_mut().iter_mut() {
+                    if let Err(error) = chart.remove_last_dataset() {
+                        println!("{}", error);
+                    }
+                }
                 println!("Remove Button pressed");
             }
+            Message::ThemeButton => {
+                self.dark_theme = !self.dark_theme;
+                let theme = if self.dark_theme {
+                    Theme::dark()
+                } else {
+                    Theme::light()
+                };
+                theme.apply_to_charts(self.charts_mut().iter_mut().map(|chart| &mut **chart));
+            }
+            Message::AnimationTick(now) => {
+                for chart in self.charts_mut().iter_mut() {
+                    chart.animation_tick(now);
+                }
+            }
+            Message::Chart(chart_index, BarChartMessage::SelectionChanged(selection)) => {
+                println!("Chart {} selection changed: {:?}", chart_index, selection);
+            }
+            Message::Chart(_, BarChartMessage::AnimationTick(_)) => {}
         }
 
         Command::none()
     }
-    /*
+
+    /// one timer for all charts, so they animate in lockstep
     fn subscription(&self) -> Subscription<Message> {
-        //time::every(std::time::Duration::from_millis(50)).map(|_| Message::Event)
-    }*/
+        if self.charts().iter().any(|chart| chart.is_animating()) {
+            time::every(ANIMATION_FRAME_INTERVAL).map(Message::AnimationTick)
+        } else {
+            Subscription::none()
+        }
+    }
 
     fn view(&mut self) -> Element<Message> {
         let width = Length::Units(400);
         let height = Length::Units(400);
 
-        let x_pos_y_pos_x = Container::new(self.x_pos_y_pos_x.chart.view())
-            .width(width)
-            .height(height);
-        let x_pos_y_neg_x = Container::new(self.x_pos_y_neg_x.chart.view())
-            .width(width)
-            .height(height);
-        let x_neg_y_pos_x = Container::new(self.x_neg_y_pos_x.chart.view())
-            .width(width)
-            .height(height);
-        let x_neg_y_neg_x = Container::new(self.x_neg_y_neg_x.chart.view())
-            .width(width)
-            .height(height);
-        let x_pos_y_pos_y = Container::new(self.x_pos_y_pos_y.chart.view())
-            .width(width)
-            .height(height);
-        let x_neg_y_pos_y = Container::new(self.x_neg_y_pos_y.chart.view())
-            .width(width)
-            .height(height);
-        let x_pos_y_neg_y = Container::new(self.x_pos_y_neg_y.chart.view())
-            .width(width)
-            .height(height);
-        let x_neg_y_neg_y = Container::new(self.x_neg_y_neg_y.chart.view())
-            .width(width)
-            .height(height);
+        let x_pos_y_pos_x = Container::new(
+            self.x_pos_y_pos_x
+                .chart
+                .view()
+                .map(move |message| Message::Chart(0, message)),
+        )
+        .width(width)
+        .height(height);
+        let x_pos_y_neg_x = Container::new(
+            self.x_pos_y_neg_x
+                .chart
+                .view()
+                .map(move |message| Message::Chart(1, message)),
+        )
+        .width(width)
+        .height(height);
+        let x_neg_y_pos_x = Container::new(
+            self.x_neg_y_pos_x
+                .chart
+                .view()
+                .map(move |message| Message::Chart(2, message)),
+        )
+        .width(width)
+        .height(height);
+        let x_neg_y_neg_x = Container::new(
+            self.x_neg_y_neg_x
+                .chart
+                .view()
+                .map(move |message| Message::Chart(3, message)),
+        )
+        .width(width)
+        .height(height);
+        let x_pos_y_pos_y = Container::new(
+            self.x_pos_y_pos_y
+                .chart
+                .view()
+                .map(move |message| Message::Chart(4, message)),
+        )
+        .width(width)
+        .height(height);
+        let x_neg_y_pos_y = Container::new(
+            self.x_neg_y_pos_y
+                .chart
+                .view()
+                .map(move |message| Message::Chart(5, message)),
+        )
+        .width(width)
+        .height(height);
+        let x_pos_y_neg_y = Container::new(
+            self.x_pos_y_neg_y
+                .chart
+                .view()
+                .map(move |message| Message::Chart(6, message)),
+        )
+        .width(width)
+        .height(height);
+        let x_neg_y_neg_y = Container::new(
+            self.x_neg_y_neg_y
+                .chart
+                .view()
+                .map(move |message| Message::Chart(7, message)),
+        )
+        .width(width)
+        .height(height);
 
-        let x_complete_y_neg_y = Container::new(self.x_complete_y_neg_y.chart.view())
-            .width(width)
-            .height(height);
-        let x_complete_y_pos_y = Container::new(self.x_complete_y_pos_y.chart.view())
-            .width(width)
-            .height(height);
-        let x_pos_y_complete_y = Container::new(self.x_pos_y_complete_y.chart.view())
-            .width(width)
-            .height(height);
-        let x_neg_y_complete_y = Container::new(self.x_neg_y_complete_y.chart.view())
-            .width(width)
-            .height(height);
+        let x_complete_y_neg_y = Container::new(
+            self.x_complete_y_neg_y
+                .chart
+                .view()
+                .map(move |message| Message::Chart(8, message)),
+        )
+        .width(width)
+        .height(height);
+        let x_complete_y_pos_y = Container::new(
+            self.x_complete_y_pos_y
+                .chart
+                .view()
+                .map(move |message| Message::Chart(9, message)),
+        )
+        .width(width)
+        .height(height);
+        let x_pos_y_complete_y = Container::new(
+            self.x_pos_y_complete_y
+                .chart
+                .view()
+                .map(move |message| Message::Chart(10, message)),
+        )
+        .width(width)
+        .height(height);
+        let x_neg_y_complete_y = Container::new(
+            self.x_neg_y_complete_y
+                .chart
+                .view()
+                .map(move |message| Message::Chart(11, message)),
+        )
+        .width(width)
+        .height(height);
         let row1 = Row::new()
             .push(x_pos_y_pos_x)
             .push(x_pos_y_neg_x)
@@ -339,6 +457,10 @@ impl Application for ChartDrawingTest {
             .push(
                 Button::new(&mut self.remove_button, Text::new("Remove Data"))
                     .on_press(Message::RemoveButton),
+            )
+            .push(
+                Button::new(&mut self.theme_button, Text::new("Toggle Dark Theme"))
+                    .on_press(Message::ThemeButton),
             );
 
         let column = Column::new().push(row1).push(row2).push(row3).push(row4);